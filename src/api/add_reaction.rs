@@ -0,0 +1,20 @@
+use serde_json::json;
+use std::error::Error;
+
+use super::{github_client, response_error};
+
+// React to an issue, or to one of its comments when a comment id is given.
+// Returns false when the user had already left this reaction
+pub async fn add_reaction(repo_owner: &str, repo_name: &str, number: i32, comment_id: Option<u64>, content: &str, access_token: &str) -> Result<bool, Box<dyn Error>> {
+    let client = github_client(access_token)?;
+    let base_url = "https://api.github.com";
+    let url = match comment_id {
+        Some(id) => format!("{}/repos/{}/{}/issues/comments/{}/reactions", base_url, repo_owner, repo_name, id),
+        None => format!("{}/repos/{}/{}/issues/{}/reactions", base_url, repo_owner, repo_name, number),
+    };
+    let response = client.post(&url).json(&json!({ "content": content })).send().await?;
+    if !response.status().is_success() {
+        return Err(response_error(response, &format!("Failed to react to #{}", number)).await);
+    }
+    Ok(response.status() == reqwest::StatusCode::CREATED)
+}
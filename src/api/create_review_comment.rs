@@ -0,0 +1,25 @@
+use crate::structs;
+use structs::{DraftReviewComment, ReviewComment};
+use serde_json::json;
+use std::error::Error;
+
+use super::{github_client, response_error};
+
+pub async fn create_review_comment(repo_owner: &str, repo_name: &str, number: i32, commit_id: &str, comment: &DraftReviewComment, access_token: &str) -> Result<ReviewComment, Box<dyn Error>> {
+    let client = github_client(access_token)?;
+    let base_url = "https://api.github.com";
+    let url = format!("{}/repos/{}/{}/pulls/{}/comments", base_url, repo_owner, repo_name, number);
+    let body = json!({
+        "body": comment.body,
+        "commit_id": commit_id,
+        "path": comment.path,
+        "line": comment.line,
+        "side": comment.side,
+    });
+    let response = client.post(&url).json(&body).send().await?;
+    if !response.status().is_success() {
+        return Err(response_error(response, "Failed to add the review comment").await);
+    }
+    let created: ReviewComment = response.json().await?;
+    Ok(created)
+}
@@ -0,0 +1,14 @@
+use std::error::Error;
+
+use super::{github_client, response_error};
+
+pub async fn delete_branch(repo_owner: &str, repo_name: &str, branch: &str, access_token: &str) -> Result<(), Box<dyn Error>> {
+    let client = github_client(access_token)?;
+    let base_url = "https://api.github.com";
+    let url = format!("{}/repos/{}/{}/git/refs/heads/{}", base_url, repo_owner, repo_name, branch);
+    let response = client.delete(&url).send().await?;
+    if !response.status().is_success() {
+        return Err(response_error(response, &format!("Failed to delete branch {}", branch)).await);
+    }
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use crate::structs;
+use structs::{CheckRuns, CiCheck, CiState, CiSummary, CombinedStatus};
+use chrono::{DateTime, Utc};
+use std::error::Error;
+
+use super::{github_client, response_error};
+
+fn seconds_between(start: &str, end: &str) -> Option<i64> {
+    let start = start.parse::<DateTime<Utc>>().ok()?;
+    let end = end.parse::<DateTime<Utc>>().ok()?;
+    Some(end.signed_duration_since(start).num_seconds())
+}
+
+fn status_state(state: &str) -> CiState {
+    match state {
+        "success" => CiState::Success,
+        "failure" | "error" => CiState::Failure,
+        _ => CiState::Pending,
+    }
+}
+
+fn check_run_state(status: &str, conclusion: Option<&str>) -> CiState {
+    match (status, conclusion) {
+        (_, Some("success")) => CiState::Success,
+        (_, Some("failure" | "timed_out" | "cancelled" | "action_required" | "startup_failure")) => CiState::Failure,
+        ("completed", _) => CiState::Neutral,
+        _ => CiState::Pending,
+    }
+}
+
+// Combined commit statuses and check runs of a commit, merged into a single list of checks
+pub async fn fetch_ci_status(repo_owner: &str, repo_name: &str, sha: &str, access_token: &str) -> Result<CiSummary, Box<dyn Error>> {
+    let client = github_client(access_token)?;
+    let base_url = "https://api.github.com";
+    let status_url = format!("{}/repos/{}/{}/commits/{}/status", base_url, repo_owner, repo_name, sha);
+    let check_runs_url = format!("{}/repos/{}/{}/commits/{}/check-runs?per_page=100", base_url, repo_owner, repo_name, sha);
+    let (status_response, check_runs_response) = futures::try_join!(
+        client.get(&status_url).send(),
+        client.get(&check_runs_url).send()
+    )?;
+    if !status_response.status().is_success() {
+        return Err(response_error(status_response, "Failed to fetch commit statuses").await);
+    }
+    if !check_runs_response.status().is_success() {
+        return Err(response_error(check_runs_response, "Failed to fetch check runs").await);
+    }
+    let combined: CombinedStatus = status_response.json().await?;
+    let check_runs: CheckRuns = check_runs_response.json().await?;
+
+    let mut checks: Vec<CiCheck> = combined.statuses
+        .iter()
+        .map(|status| CiCheck {
+            name: status.context.clone(),
+            state: status_state(&status.state),
+            conclusion: status.description.clone().unwrap_or_else(|| status.state.clone()),
+            duration: seconds_between(&status.created_at, &status.updated_at),
+            url: status.target_url.clone(),
+        })
+        .collect();
+    checks.extend(check_runs.check_runs.iter().map(|run| CiCheck {
+        name: run.name.clone(),
+        state: check_run_state(&run.status, run.conclusion.as_deref()),
+        conclusion: run.conclusion.clone().unwrap_or_else(|| run.status.clone()),
+        duration: match (&run.started_at, &run.completed_at) {
+            (Some(start), Some(end)) => seconds_between(start, end),
+            _ => None,
+        },
+        url: run.details_url.clone().or_else(|| run.html_url.clone()),
+    }));
+
+    let state = if checks.iter().any(|c| c.state == CiState::Failure) {
+        CiState::Failure
+    } else if checks.iter().any(|c| c.state == CiState::Pending) {
+        CiState::Pending
+    } else if checks.is_empty() {
+        CiState::Neutral
+    } else {
+        CiState::Success
+    };
+
+    Ok(CiSummary { sha: sha.to_string(), state, checks })
+}
@@ -1,39 +1,24 @@
-use reqwest::header::{HeaderValue, ACCEPT};
-use reqwest::header;
-
 use crate::structs;
 use structs::{ApiResponse, IssueComments};
 use std::error::Error;
 
+use super::{github_client, response_error};
 
-pub async fn get_github_response(username: &str, access_token: &str, status: &str) -> Result<ApiResponse, Box<dyn Error>> {
-    let mut headers = header::HeaderMap::new();
-    headers.insert(
-        ACCEPT,
-        HeaderValue::from_static("application/vnd.github.v3+json"),
-    );
-    headers.insert(
-        "Authorization",
-        HeaderValue::from_str(&format!("Bearer {}", access_token)).unwrap(),
-    );
-    headers.insert("User-Agent", HeaderValue::from_static("my app"));
-    let client = reqwest::Client::builder()
-        .default_headers(headers)
-        .build()?;
+// Run an issue search query, e.g. "assignee:octocat state:open", and fetch the comments of the open results
+pub async fn get_github_response(query: &str, access_token: &str) -> Result<ApiResponse, Box<dyn Error>> {
+    let client = github_client(access_token)?;
     let base_url = "https://api.github.com";
-    let url = format!(
-        "{}/search/issues?q=assignee:{}+state:{}&per_page=100
-        ",
-        base_url, username, status
-    );
-    let github_response = client
+    let url = format!("{}/search/issues", base_url);
+    let response = client
         .get(url)
+        .query(&[("q", query), ("per_page", "100")])
         .send()
-        .await?
-        .text()
         .await?;
-    let mut items: ApiResponse = serde_json::from_str(&github_response)?;
-
+    // Invalid queries and rate limits come back with a message worth showing instead of the search results
+    if !response.status().is_success() {
+        return Err(response_error(response, &format!("Search \"{}\" failed", query)).await);
+    }
+    let mut items: ApiResponse = response.json().await?;
     for item in items.items.iter_mut() {
         let url_parts: Vec<&str> = item.url.split("/").collect();
         item.repository = Some(url_parts[url_parts.len() - 3].to_string());
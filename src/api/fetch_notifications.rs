@@ -0,0 +1,45 @@
+use reqwest::header::{IF_MODIFIED_SINCE, LAST_MODIFIED};
+use reqwest::StatusCode;
+
+use crate::structs;
+use structs::Notification;
+use structs::inbox::DEFAULT_POLL_INTERVAL;
+use std::error::Error;
+
+use super::{github_client, response_error};
+
+// Fetch the notification threads of the user, read ones included.
+// Returns None as notifications when nothing changed since `last_modified`, along with
+// the poll interval GitHub asks for and the Last-Modified value to send on the next poll
+pub async fn fetch_notifications(last_modified: Option<&str>, access_token: &str) -> Result<(Option<Vec<Notification>>, u64, Option<String>), Box<dyn Error>> {
+    let client = github_client(access_token)?;
+    let base_url = "https://api.github.com";
+    let url = format!("{}/notifications?all=true&per_page=50", base_url);
+    let mut request = client.get(&url);
+    if let Some(last_modified) = last_modified {
+        request = request.header(IF_MODIFIED_SINCE, last_modified);
+    }
+    let response = request.send().await?;
+
+    let poll_interval = response
+        .headers()
+        .get("X-Poll-Interval")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_POLL_INTERVAL);
+    let new_last_modified = response
+        .headers()
+        .get(LAST_MODIFIED)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string())
+        .or_else(|| last_modified.map(|value| value.to_string()));
+
+    if response.status() == StatusCode::NOT_MODIFIED {
+        return Ok((None, poll_interval, new_last_modified));
+    }
+    if !response.status().is_success() {
+        return Err(response_error(response, "Failed to fetch notifications").await);
+    }
+    let notifications: Vec<Notification> = response.json().await?;
+    Ok((Some(notifications), poll_interval, new_last_modified))
+}
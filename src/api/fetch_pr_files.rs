@@ -0,0 +1,31 @@
+use crate::structs;
+use structs::PrFile;
+use std::error::Error;
+
+use super::{github_client, response_error};
+
+pub async fn fetch_pr_files(repo_owner: &str, repo_name: &str, number: i32, access_token: &str) -> Result<Vec<PrFile>, Box<dyn Error>> {
+    let client = github_client(access_token)?;
+    let base_url = "https://api.github.com";
+    let mut files: Vec<PrFile> = vec![];
+    let mut page = 1;
+    // The endpoint is paginated and caps at 3000 files, fetch until a page comes back short
+    loop {
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}/files?per_page=100&page={}",
+            base_url, repo_owner, repo_name, number, page
+        );
+        let response = client.get(&url).send().await?;
+        if !response.status().is_success() {
+            return Err(response_error(response, &format!("Failed to fetch files for PR #{}", number)).await);
+        }
+        let page_files: Vec<PrFile> = response.json().await?;
+        let page_len = page_files.len();
+        files.extend(page_files);
+        if page_len < 100 || page >= 30 {
+            break;
+        }
+        page += 1;
+    }
+    Ok(files)
+}
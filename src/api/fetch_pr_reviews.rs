@@ -0,0 +1,17 @@
+use crate::structs;
+use structs::PullRequestReview;
+use std::error::Error;
+
+use super::{github_client, response_error};
+
+pub async fn fetch_pr_reviews(repo_owner: &str, repo_name: &str, number: i32, access_token: &str) -> Result<Vec<PullRequestReview>, Box<dyn Error>> {
+    let client = github_client(access_token)?;
+    let base_url = "https://api.github.com";
+    let url = format!("{}/repos/{}/{}/pulls/{}/reviews?per_page=100", base_url, repo_owner, repo_name, number);
+    let response = client.get(&url).send().await?;
+    if !response.status().is_success() {
+        return Err(response_error(response, &format!("Failed to fetch reviews for PR #{}", number)).await);
+    }
+    let reviews: Vec<PullRequestReview> = response.json().await?;
+    Ok(reviews)
+}
@@ -0,0 +1,31 @@
+use crate::structs;
+use structs::PullRequest;
+use std::error::Error;
+use std::time::Duration;
+
+use super::{github_client, response_error};
+
+pub async fn fetch_pull_request(repo_owner: &str, repo_name: &str, number: i32, access_token: &str) -> Result<PullRequest, Box<dyn Error>> {
+    let client = github_client(access_token)?;
+    let base_url = "https://api.github.com";
+    let url = format!("{}/repos/{}/{}/pulls/{}", base_url, repo_owner, repo_name, number);
+    let response = client.get(&url).send().await?;
+    if !response.status().is_success() {
+        return Err(response_error(response, &format!("Failed to fetch PR #{}", number)).await);
+    }
+    let pull_request: PullRequest = response.json().await?;
+    Ok(pull_request)
+}
+
+// Fetch again while GitHub is still computing the mergeability, it usually takes a second or two
+pub async fn fetch_mergeable_pull_request(repo_owner: &str, repo_name: &str, number: i32, access_token: &str) -> Result<PullRequest, Box<dyn Error>> {
+    let mut pull_request = fetch_pull_request(repo_owner, repo_name, number, access_token).await?;
+    for _ in 0..4 {
+        if !pull_request.mergeability_pending() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        pull_request = fetch_pull_request(repo_owner, repo_name, number, access_token).await?;
+    }
+    Ok(pull_request)
+}
@@ -0,0 +1,77 @@
+use crate::structs;
+use structs::ReviewComment;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::error::Error;
+
+use super::{github_client, response_error};
+
+// The REST API does not expose whether a thread is resolved, ask GraphQL for the ids of resolved thread roots
+async fn fetch_resolved_threads(client: &reqwest::Client, repo_owner: &str, repo_name: &str, number: i32) -> Result<HashSet<i64>, Box<dyn Error>> {
+    let query = r#"
+        query($owner: String!, $name: String!, $number: Int!) {
+          repository(owner: $owner, name: $name) {
+            pullRequest(number: $number) {
+              reviewThreads(first: 100) {
+                nodes { isResolved comments(first: 1) { nodes { databaseId } } }
+              }
+            }
+          }
+        }
+    "#;
+    let body = json!({
+        "query": query,
+        "variables": { "owner": repo_owner, "name": repo_name, "number": number },
+    });
+    let response: Value = client
+        .post("https://api.github.com/graphql")
+        .json(&body)
+        .send()
+        .await?
+        .json()
+        .await?;
+    let threads = response["data"]["repository"]["pullRequest"]["reviewThreads"]["nodes"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    let resolved = threads
+        .iter()
+        .filter(|thread| thread["isResolved"].as_bool().unwrap_or(false))
+        .filter_map(|thread| thread["comments"]["nodes"][0]["databaseId"].as_i64())
+        .collect();
+    Ok(resolved)
+}
+
+pub async fn fetch_review_comments(repo_owner: &str, repo_name: &str, number: i32, access_token: &str) -> Result<Vec<ReviewComment>, Box<dyn Error>> {
+    let client = github_client(access_token)?;
+    let base_url = "https://api.github.com";
+    let mut comments: Vec<ReviewComment> = vec![];
+    let mut page = 1;
+    // Same cap as the files, 30 pages of 100 comments
+    loop {
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}/comments?per_page=100&page={}",
+            base_url, repo_owner, repo_name, number, page
+        );
+        let response = client.get(&url).send().await?;
+        if !response.status().is_success() {
+            return Err(response_error(response, &format!("Failed to fetch review comments for PR #{}", number)).await);
+        }
+        let page_comments: Vec<ReviewComment> = response.json().await?;
+        let page_len = page_comments.len();
+        comments.extend(page_comments);
+        if page_len < 100 || page >= 30 {
+            break;
+        }
+        page += 1;
+    }
+
+    // Resolution state is a nice to have, keep the comments if the GraphQL call fails
+    let resolved = fetch_resolved_threads(&client, repo_owner, repo_name, number)
+        .await
+        .unwrap_or_default();
+    for comment in comments.iter_mut() {
+        comment.resolved = resolved.contains(&comment.thread_id());
+    }
+    Ok(comments)
+}
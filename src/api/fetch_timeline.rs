@@ -0,0 +1,31 @@
+use crate::structs;
+use structs::TimelineEvent;
+use std::error::Error;
+
+use super::{github_client, response_error};
+
+pub async fn fetch_timeline(repo_owner: &str, repo_name: &str, number: i32, access_token: &str) -> Result<Vec<TimelineEvent>, Box<dyn Error>> {
+    let client = github_client(access_token)?;
+    let base_url = "https://api.github.com";
+    let mut events: Vec<TimelineEvent> = vec![];
+    let mut page = 1;
+    // Long lived issues have hundreds of events, stop after 1000
+    loop {
+        let url = format!(
+            "{}/repos/{}/{}/issues/{}/timeline?per_page=100&page={}",
+            base_url, repo_owner, repo_name, number, page
+        );
+        let response = client.get(&url).send().await?;
+        if !response.status().is_success() {
+            return Err(response_error(response, &format!("Failed to fetch the timeline of #{}", number)).await);
+        }
+        let page_events: Vec<TimelineEvent> = response.json().await?;
+        let page_len = page_events.len();
+        events.extend(page_events);
+        if page_len < 100 || page >= 10 {
+            break;
+        }
+        page += 1;
+    }
+    Ok(events)
+}
@@ -0,0 +1,14 @@
+use std::error::Error;
+
+use super::{github_client, response_error};
+
+pub async fn mark_notification_done(thread_id: &str, access_token: &str) -> Result<(), Box<dyn Error>> {
+    let client = github_client(access_token)?;
+    let base_url = "https://api.github.com";
+    let url = format!("{}/notifications/threads/{}", base_url, thread_id);
+    let response = client.delete(&url).send().await?;
+    if !response.status().is_success() {
+        return Err(response_error(response, "Failed to mark the notification as done").await);
+    }
+    Ok(())
+}
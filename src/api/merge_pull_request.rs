@@ -0,0 +1,31 @@
+use serde_json::json;
+use std::error::Error;
+
+use super::{github_client, response_error};
+
+#[allow(clippy::too_many_arguments)]
+pub async fn merge_pull_request(repo_owner: &str, repo_name: &str, number: i32, head_sha: &str, merge_method: &str, commit_title: &str, commit_message: &str, access_token: &str) -> Result<(), Box<dyn Error>> {
+    let client = github_client(access_token)?;
+    let base_url = "https://api.github.com";
+    let url = format!("{}/repos/{}/{}/pulls/{}/merge", base_url, repo_owner, repo_name, number);
+    let mut body = json!({
+        "merge_method": merge_method,
+        // Refuse to merge if the head moved since the merge form was opened
+        "sha": head_sha,
+    });
+    // Rebase merges keep the original commits, there is no merge commit to describe
+    if merge_method != "rebase" {
+        body["commit_title"] = json!(commit_title);
+        body["commit_message"] = json!(commit_message);
+    }
+    let response = client.put(&url).json(&body).send().await?;
+    if !response.status().is_success() {
+        let context = match response.status().as_u16() {
+            405 => "Pull request cannot be merged",
+            409 => "The head branch was updated, reopen the merge popup",
+            _ => "Failed to merge the pull request",
+        };
+        return Err(response_error(response, context).await);
+    }
+    Ok(())
+}
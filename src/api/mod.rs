@@ -1,41 +1,167 @@
 use crate::structs;
-use structs::{ApiResponseItem};
+use structs::{ApiResponseItem, IssueTab, SavedSearch};
 use std::{error::Error};
+use reqwest::header::{HeaderValue, ACCEPT};
+use reqwest::header;
 mod fetch_github_data;
 mod fetch_github_pr_review;
+mod fetch_pr_files;
+mod fetch_pull_request;
+mod fetch_review_comments;
+mod create_review_comment;
+mod submit_review;
+mod merge_pull_request;
+mod delete_branch;
+mod fetch_ci_status;
+mod fetch_pr_reviews;
 mod update_issue_status;
+mod fetch_notifications;
+mod mark_notification_read;
+mod mark_notification_done;
+mod unsubscribe_notification;
+mod fetch_timeline;
+mod add_reaction;
 use fetch_github_data::get_github_response;
 use fetch_github_pr_review::fetch_github_pr_review;
+use fetch_pr_reviews::fetch_pr_reviews;
 use chrono::{DateTime, NaiveDateTime, Utc};
+use futures::stream::{self, StreamExt};
+
+fn github_client(access_token: &str) -> Result<reqwest::Client, Box<dyn Error>> {
+  let mut headers = header::HeaderMap::new();
+  headers.insert(
+      ACCEPT,
+      HeaderValue::from_static("application/vnd.github.v3+json"),
+  );
+  headers.insert(
+      "Authorization",
+      HeaderValue::from_str(&format!("Bearer {}", access_token))?,
+  );
+  headers.insert("User-Agent", HeaderValue::from_static("my app"));
+  let client = reqwest::Client::builder()
+      .default_headers(headers)
+      .build()?;
+  Ok(client)
+}
+
+// Build an error out of a failed response, using the message GitHub sends back when there is one
+async fn response_error(response: reqwest::Response, context: &str) -> Box<dyn Error> {
+  let status = response.status();
+  let message = response
+      .json::<serde_json::Value>()
+      .await
+      .ok()
+      .and_then(|body| body["message"].as_str().map(|m| m.to_string()))
+      .unwrap_or_else(|| status.to_string());
+  format!("{}: {}", context, message).into()
+}
 
 fn parse_date_string(date_string: &str) -> DateTime<Utc> {
   let naive_date = NaiveDateTime::parse_from_str(date_string, "%Y-%m-%dT%H:%M:%SZ").unwrap();
   DateTime::from_naive_utc_and_offset(naive_date, Utc)
 }
 
-pub async fn init_gh_data(username: &str, access_token: &str) -> Result<(Vec<ApiResponseItem>, Vec<ApiResponseItem>, Vec<ApiResponseItem>, i32, i32, i32), Box<dyn Error>> {
+// Pull requests whose details are loaded at the same time, each of them takes 4 requests
+const PR_DETAILS_CONCURRENCY: usize = 6;
+
+// Fetch the pull request data the search API does not return: details, reviews and CI status of the head commit.
+// Only open pull requests show them, closed and merged ones would cost 4 requests each for nothing
+async fn attach_pr_details(items: &mut [ApiResponseItem], access_token: &str) {
+  let requests = items.iter().enumerate().filter(|(_, item)| item.is_pr && item.state == "open").map(|(index, item)| async move {
+      let repo_owner = item.organization.clone().unwrap_or_default();
+      let repo_name = item.repository.clone().unwrap_or_default();
+      let (pull_request, reviews) = futures::join!(
+          fetch_pull_request(&repo_owner, &repo_name, item.number, access_token),
+          fetch_pr_reviews(&repo_owner, &repo_name, item.number, access_token)
+      );
+      let mut pull_request = pull_request.ok()?;
+      pull_request.reviews = reviews.unwrap_or_default();
+      let ci_status = fetch_ci_status(&repo_owner, &repo_name, &pull_request.head.sha, access_token).await.ok();
+      Some((index, pull_request, ci_status))
+  });
+  let details: Vec<_> = stream::iter(requests).buffer_unordered(PR_DETAILS_CONCURRENCY).collect().await;
+  for (index, pull_request, ci_status) in details.into_iter().flatten() {
+      items[index].pr_details = Some(pull_request);
+      items[index].ci_status = ci_status;
+  }
+}
+
+// Sort search results with the most recently updated first
+fn sort_by_updated(items: &mut [ApiResponseItem]) {
+  items.sort_by_key(|i| parse_date_string(&i.updated_at));
+  items.reverse();
+}
+
+// Run a saved search and load the details of the pull requests it returns
+pub async fn fetch_search_tab(search: &SavedSearch, access_token: &str) -> Result<IssueTab, Box<dyn Error>> {
+  let response = get_github_response(&search.query, access_token).await?;
+  let mut items = response.items.to_owned();
+  sort_by_updated(&mut items);
+  attach_pr_details(&mut items, access_token).await;
+  Ok(IssueTab::new(&search.name, items, response.total_count))
+}
+
+// Returns the Assignments, Closed, To Review, Authored and Mentions tabs, in that order, followed by the saved searches
+pub async fn init_gh_data(username: &str, access_token: &str, saved_searches: &[SavedSearch]) -> Result<Vec<IssueTab>, Box<dyn Error>> {
   // Get list of open issues
-  let issues_list_response_open = get_github_response(username, access_token, "open").await?;
+  let issues_list_response_open = get_github_response(&format!("assignee:{} state:open", username), access_token).await?;
   let mut issues_list_open = issues_list_response_open.items.to_owned();
-  issues_list_open.sort_by_key(|i| parse_date_string(&i.updated_at));
-  issues_list_open.reverse();
+  sort_by_updated(&mut issues_list_open);
+  attach_pr_details(&mut issues_list_open, access_token).await;
   // Get list of closed issues
-  let issues_list_response_closed = get_github_response(username, access_token, "closed").await?;
+  let issues_list_response_closed = get_github_response(&format!("assignee:{} state:closed", username), access_token).await?;
   let mut issues_list_closed = issues_list_response_closed.items.to_owned();
-  issues_list_closed.sort_by_key(|i| parse_date_string(&i.updated_at));
-  issues_list_closed.reverse();
+  sort_by_updated(&mut issues_list_closed);
+  attach_pr_details(&mut issues_list_closed, access_token).await;
   // Get list of Assigned for review PR
   let assigned_pr = fetch_github_pr_review(username, access_token).await?;
   let mut assigned_pr_list = assigned_pr.items.to_owned();
-  assigned_pr_list.sort_by_key(|i| parse_date_string(&i.updated_at));
-  assigned_pr_list.reverse();
-
-  // Convert the lengths of the objects lists to i32
-  let issues_list_open_len = issues_list_response_open.total_count;
-  let issues_list_closed_len = issues_list_response_closed.total_count;
-  let assigned_pr_list_len = assigned_pr.total_count;
+  sort_by_updated(&mut assigned_pr_list);
+  attach_pr_details(&mut assigned_pr_list, access_token).await;
+  // Get list of open PRs authored by the user
+  let authored_pr = get_github_response(&format!("author:{} type:pr state:open", username), access_token).await?;
+  let mut authored_pr_list = authored_pr.items.to_owned();
+  sort_by_updated(&mut authored_pr_list);
+  attach_pr_details(&mut authored_pr_list, access_token).await;
+  // Get list of open threads the user is involved in (mentioned, commented) without being assigned
+  let involved = get_github_response(&format!("involves:{} -assignee:{} state:open", username, username), access_token).await?;
+  let mut involved_list = involved.items.to_owned();
+  sort_by_updated(&mut involved_list);
+  attach_pr_details(&mut involved_list, access_token).await;
 
-  Ok((issues_list_open, issues_list_closed, assigned_pr_list, issues_list_open_len, issues_list_closed_len, assigned_pr_list_len))
+  let mut tabs = vec![
+      IssueTab::new("Assignments", issues_list_open, issues_list_response_open.total_count),
+      IssueTab::new("Closed", issues_list_closed, issues_list_response_closed.total_count),
+      IssueTab::new("To Review", assigned_pr_list, assigned_pr.total_count),
+      IssueTab::new("Authored", authored_pr_list, authored_pr.total_count),
+      IssueTab::new("Mentions", involved_list, involved.total_count),
+  ];
+  // A broken saved search only empties its own tab
+  for search in saved_searches {
+      match fetch_search_tab(search, access_token).await {
+          Ok(tab) => tabs.push(tab),
+          Err(e) => {
+              let mut tab = IssueTab::new(&search.name, vec![], 0);
+              tab.error = Some(e.to_string());
+              tabs.push(tab);
+          }
+      }
+  }
+  Ok(tabs)
 }
 
 pub use update_issue_status::update_issue_status;
+pub use fetch_pr_files::fetch_pr_files;
+pub use fetch_review_comments::fetch_review_comments;
+pub use create_review_comment::create_review_comment;
+pub use submit_review::submit_review;
+pub use merge_pull_request::merge_pull_request;
+pub use delete_branch::delete_branch;
+pub use fetch_pull_request::{fetch_pull_request, fetch_mergeable_pull_request};
+pub use fetch_ci_status::fetch_ci_status;
+pub use fetch_notifications::fetch_notifications;
+pub use mark_notification_read::mark_notification_read;
+pub use mark_notification_done::mark_notification_done;
+pub use unsubscribe_notification::unsubscribe_notification;
+pub use fetch_timeline::fetch_timeline;
+pub use add_reaction::add_reaction;
@@ -0,0 +1,25 @@
+use crate::structs;
+use structs::DraftReviewComment;
+use serde_json::json;
+use std::error::Error;
+
+use super::{github_client, response_error};
+
+// event is one of APPROVE, REQUEST_CHANGES or COMMENT
+#[allow(clippy::too_many_arguments)]
+pub async fn submit_review(repo_owner: &str, repo_name: &str, number: i32, commit_id: &str, event: &str, body: &str, comments: &[DraftReviewComment], access_token: &str) -> Result<(), Box<dyn Error>> {
+    let client = github_client(access_token)?;
+    let base_url = "https://api.github.com";
+    let url = format!("{}/repos/{}/{}/pulls/{}/reviews", base_url, repo_owner, repo_name, number);
+    let body = json!({
+        "commit_id": commit_id,
+        "body": body,
+        "event": event,
+        "comments": comments,
+    });
+    let response = client.post(&url).json(&body).send().await?;
+    if !response.status().is_success() {
+        return Err(response_error(response, "Failed to submit the review").await);
+    }
+    Ok(())
+}
@@ -0,0 +1,15 @@
+use std::error::Error;
+
+use super::{github_client, response_error};
+
+// Stop receiving notifications for the thread, GitHub subscribes again on a new mention
+pub async fn unsubscribe_notification(thread_id: &str, access_token: &str) -> Result<(), Box<dyn Error>> {
+    let client = github_client(access_token)?;
+    let base_url = "https://api.github.com";
+    let url = format!("{}/notifications/threads/{}/subscription", base_url, thread_id);
+    let response = client.delete(&url).send().await?;
+    if !response.status().is_success() {
+        return Err(response_error(response, "Failed to unsubscribe from the thread").await);
+    }
+    Ok(())
+}
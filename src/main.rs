@@ -1,32 +1,61 @@
 mod structs;
-use structs::ApiResponseItem;
+use structs::{ Action, ApiResponseItem, Config, DetailsPane, DiffView, DraftReviewComment, Filter, HelpView, Inbox, IssueTab, Key, KeyContext, Keymap, MergeForm, Popup, Priorities, ReactionPicker, SavedSearch, Staleness, TextInput, Theme, TimelineView, REACTION_KINDS };
 
 mod api;
-use api::{ init_gh_data, update_issue_status };
+use api::{
+    init_gh_data,
+    fetch_search_tab,
+    update_issue_status,
+    fetch_pr_files,
+    fetch_pull_request,
+    fetch_mergeable_pull_request,
+    fetch_review_comments,
+    create_review_comment,
+    submit_review,
+    merge_pull_request,
+    delete_branch,
+    fetch_ci_status,
+    fetch_timeline,
+    add_reaction,
+    fetch_notifications,
+    mark_notification_read,
+    mark_notification_done,
+    unsubscribe_notification,
+};
 
 mod render_items;
-use render_items::{ render_home, render_issues, render_waiting_screen, render_popup, render_error };
+use render_items::{
+    render_home,
+    render_issues,
+    render_waiting_screen,
+    render_popup,
+    render_error,
+    render_diff,
+    render_input,
+    render_merge,
+    render_checks,
+    render_timeline,
+    render_reactions,
+    render_inbox,
+    render_help,
+};
 
 mod utils;
 use utils::{
-    get_current_state_and_list,
     get_current_state_repo_org_list,
     move_selection,
     move_selection_org_repo,
     get_org_list,
     get_repo_list,
-    filter_issues_by_state,
-    filter_issues_by_org,
-    filter_issues_by_repo,
+    get_label_list,
 };
 
 use dotenv::dotenv;
-use tokio;
 use std::{ error::Error, sync::mpsc };
 use tui::{
     backend::CrosstermBackend,
     layout::{ Alignment, Constraint, Direction, Layout },
-    style::{ Color, Modifier, Style },
+    style::{ Modifier, Style },
     text::{ Span, Spans },
     widgets::{ Block, BorderType, Borders, ListState, Paragraph, Tabs },
     Terminal,
@@ -44,27 +73,176 @@ enum Event<I> {
     Tick,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 enum MenuItem {
     Home,
     Assignments,
     Closed,
-    Refresh,
     ToReview,
+    Authored,
+    Mentions,
+    Inbox,
+    // Saved search defined in the config, by position
+    Custom(usize),
+    // Results of the last query typed in the search bar
+    Search,
+}
+
+impl MenuItem {
+    // Position of the tab in the lists returned by init_gh_data
+    fn tab_index(&self) -> Option<usize> {
+        match self {
+            MenuItem::Home => None,
+            MenuItem::Assignments => Some(0),
+            MenuItem::Closed => Some(1),
+            MenuItem::ToReview => Some(2),
+            MenuItem::Authored => Some(3),
+            MenuItem::Mentions => Some(4),
+            MenuItem::Inbox => None,
+            MenuItem::Custom(index) => Some(5 + index),
+            MenuItem::Search => None,
+        }
+    }
+
+    fn from_tab_index(index: usize) -> MenuItem {
+        match index {
+            0 => MenuItem::Assignments,
+            1 => MenuItem::Closed,
+            2 => MenuItem::ToReview,
+            3 => MenuItem::Authored,
+            4 => MenuItem::Mentions,
+            index => MenuItem::Custom(index - 5),
+        }
+    }
+
+    fn empty_message(&self) -> &'static str {
+        match self {
+            MenuItem::Closed => "No closed issues found",
+            MenuItem::ToReview => "No Assigned PR",
+            MenuItem::Authored => "No open pull request authored",
+            MenuItem::Mentions => "No open thread mentioning you",
+            MenuItem::Custom(_) | MenuItem::Search => "No results for this search",
+            _ => "No assigned issues found",
+        }
+    }
+}
+
+// Tabs in menu order, the saved searches come after the built-in ones
+fn menu_items(config: &Config, has_search: bool) -> Vec<MenuItem> {
+    let mut items = vec![
+        MenuItem::Home,
+        MenuItem::Assignments,
+        MenuItem::Closed,
+        MenuItem::ToReview,
+        MenuItem::Authored,
+        MenuItem::Mentions,
+        MenuItem::Inbox,
+    ];
+    items.extend((0..config.tabs.len()).map(MenuItem::Custom));
+    if has_search {
+        items.push(MenuItem::Search);
+    }
+    items
+}
+
+fn menu_titles(config: &Config, has_search: bool) -> Vec<String> {
+    let mut titles: Vec<String> = ["Home", "Assignments", "Closed", "To Review", "Authored", "Mentions", "Inbox"]
+        .iter()
+        .map(|title| title.to_string())
+        .collect();
+    titles.extend(config.tabs.iter().map(|tab| tab.name.clone()));
+    if has_search {
+        titles.push("Search".to_string());
+    }
+    titles.push("Quit".to_string());
+    titles
+}
+
+// Next or previous tab of the menu, wrapping around
+fn cycle_menu_item(current: MenuItem, config: &Config, has_search: bool, delta: isize) -> MenuItem {
+    let items = menu_items(config, has_search);
+    let position = items.iter().position(|item| *item == current).unwrap_or(0) as isize;
+    items[(position + delta).rem_euclid(items.len() as isize) as usize]
+}
+
+fn current_tab<'a>(
+    menu_item: MenuItem,
+    tabs: &'a mut [IssueTab],
+    search_tab: &'a mut Option<IssueTab>
+) -> Option<&'a mut IssueTab> {
+    match menu_item {
+        MenuItem::Search => search_tab.as_mut(),
+        _ => menu_item.tab_index().and_then(move |i| tabs.get_mut(i)),
+    }
+}
+
+// Add a filter to the stack, in place of the one of the same kind it replaces
+fn push_filter(filters: &mut Vec<Filter>, filter: Filter) {
+    filters.retain(|existing| !filter.replaces(existing) && *existing != filter);
+    filters.push(filter);
 }
 
-impl From<MenuItem> for usize {
-    fn from(input: MenuItem) -> usize {
-        match input {
-            MenuItem::Home => 0,
-            MenuItem::Assignments => 1,
-            MenuItem::Closed => 2,
-            MenuItem::Refresh => 3,
-            MenuItem::ToReview => 4,
+fn apply_filters(filters: &[Filter], staleness: &Staleness, tabs: &mut [IssueTab], search_tab: &mut Option<IssueTab>) {
+    for tab in tabs.iter_mut().chain(search_tab.iter_mut()) {
+        tab.set_filters(filters, staleness);
+    }
+}
+
+fn apply_priorities(priorities: &Priorities, tabs: &mut [IssueTab], search_tab: &mut Option<IssueTab>) {
+    for tab in tabs.iter_mut().chain(search_tab.iter_mut()) {
+        tab.set_priorities(priorities);
+    }
+}
+
+// What the text typed in the input popup is used for
+enum InputTarget {
+    ReviewComment(DraftReviewComment),
+    PendingReviewComment(DraftReviewComment),
+    ReviewSummary(&'static str),
+    SearchQuery,
+    // Name of the saved tab the query is promoted to
+    SaveSearch(String),
+    Filters,
+}
+
+impl InputTarget {
+    fn title(&self) -> String {
+        match self {
+            InputTarget::ReviewComment(draft) => format!("Comment on {}:{}", draft.path, draft.line),
+            InputTarget::PendingReviewComment(draft) =>
+                format!("Add to pending review: {}:{}", draft.path, draft.line),
+            InputTarget::ReviewSummary(_) => "Review summary".to_string(),
+            InputTarget::SearchQuery =>
+                "Search issues and pull requests, e.g. repo:org/x label:bug is:open - UP / DOWN: history".to_string(),
+            InputTarget::SaveSearch(query) => format!("Name of the tab for: {}", query),
+            InputTarget::Filters =>
+                "Add filters: org:x repo:y is:pr is:issue is:stale label:bug author:someone age:>30 age:<7".to_string(),
         }
     }
 }
 
+const REVIEW_EVENTS: [(&str, &str); 3] = [
+    ("Comment", "COMMENT"),
+    ("Approve", "APPROVE"),
+    ("Request changes", "REQUEST_CHANGES"),
+];
+
+// Keymap contexts of the current screen, the global bindings apply everywhere
+fn key_contexts(menu_item: MenuItem, diff_open: bool) -> Vec<KeyContext> {
+    let context = match menu_item {
+        _ if diff_open => KeyContext::Diff,
+        MenuItem::Home => return vec![KeyContext::Global],
+        MenuItem::Inbox => KeyContext::Inbox,
+        _ => KeyContext::List,
+    };
+    vec![context, KeyContext::Global]
+}
+
+// Position of the tab in the menu titles, which list the menu items in order
+fn menu_index(menu_item: MenuItem, config: &Config) -> usize {
+    menu_items(config, true).iter().position(|item| *item == menu_item).unwrap_or(0)
+}
+
 fn init_variables() -> (String, String) {
     dotenv().ok();
     let username = std::env
@@ -77,11 +255,33 @@ fn init_variables() -> (String, String) {
         .expect(
             "GITHUB_TOKEN must be set. Make sure you run export GITHUB_TOKEN='your github token'"
         );
-    return (username, access_token);
+    (username, access_token)
+}
+
+// Refresh the inbox, errors are kept in the inbox so a token without the notifications scope does not stop the app
+async fn poll_notifications(inbox: &mut Inbox, access_token: &str) {
+    inbox.last_poll = Instant::now();
+    match fetch_notifications(inbox.last_modified.as_deref(), access_token).await {
+        Ok((notifications, poll_interval, last_modified)) => {
+            inbox.poll_interval = poll_interval;
+            inbox.last_modified = last_modified;
+            inbox.error = None;
+            if let Some(notifications) = notifications {
+                inbox.set_notifications(notifications);
+            }
+        }
+        Err(e) => {
+            inbox.error = Some(e.to_string());
+        }
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
+    let mut config = Config::load()?;
+    let mut priorities = Priorities::load()?;
+    let theme = Theme::from_config(&config.theme)?;
+    let mut keymap = Keymap::from_config(&config.keys)?;
     enable_raw_mode().expect("can run in raw mode");
     let (username, access_token) = init_variables();
     let (tx, rx) = mpsc::channel();
@@ -99,10 +299,8 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 }
             }
 
-            if last_tick.elapsed() >= tick_rate {
-                if let Ok(_) = tx.send(Event::Tick) {
-                    last_tick = Instant::now();
-                }
+            if last_tick.elapsed() >= tick_rate && tx.send(Event::Tick).is_ok() {
+                last_tick = Instant::now();
             }
         }
     });
@@ -112,28 +310,15 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?;
     // Render the loading screen
-    render_waiting_screen(&mut terminal)?;
-
-    let (
-        mut issues_list_open,
-        mut issues_list_closed,
-        mut assigned_pr_list,
-        mut issues_list_open_len,
-        mut issues_list_closed_len,
-        mut assigned_pr_list_len,
-    ) = init_gh_data(&username, &access_token).await?;
-
-    let menu_titles = vec!["Home", "Assignments", "Closed", "Refresh", "To Review", "Quit"];
-    let mut active_menu_item = MenuItem::Home;
+    render_waiting_screen(&mut terminal, &theme)?;
 
-    let mut issue_list_state_open = ListState::default();
-    issue_list_state_open.select(Some(0));
+    let mut issue_tabs = init_gh_data(&username, &access_token, &config.tabs).await?;
 
-    let mut issue_list_state_closed = ListState::default();
-    issue_list_state_closed.select(Some(0));
+    // Notification threads, polled again whenever GitHub allows it
+    let mut inbox = Inbox::default();
+    poll_notifications(&mut inbox, &access_token).await;
 
-    let mut issue_list_state_to_review = ListState::default();
-    issue_list_state_to_review.select(Some(0));
+    let mut active_menu_item = MenuItem::Home;
 
     let mut action_list_state = ListState::default();
     action_list_state.select(Some(0));
@@ -141,18 +326,63 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let mut org_or_repo_list = ListState::default();
     org_or_repo_list.select(Some(0));
 
-    let mut active_open = true;
     let mut show_comment = false;
-    let mut to_review_open = false;
+    let mut details_pane = DetailsPane::default();
 
     // Create a flag to keep track of whether the prompt window is open
     let mut prompt_open = false;
     let mut show_org_modal = false;
     let mut show_repo_modal = false;
+    let mut show_label_modal = false;
     let mut org_list: Vec<String> = vec![];
     let mut repo_list: Vec<String> = vec![];
+    let mut label_list: Vec<(String, usize)> = vec![];
+
+    // Diff viewer for the selected pull request
+    let mut diff_view: Option<DiffView> = None;
+    let mut review_popup_open = false;
+    let mut review_event_state = ListState::default();
+    review_event_state.select(Some(0));
+
+    // Merge options for the pull request being merged
+    let mut merge_form: Option<MergeForm> = None;
+
+    // CI checks popup of a pull request
+    let mut checks_item: Option<ApiResponseItem> = None;
+    let mut timeline_view: Option<TimelineView> = None;
+    let mut reaction_picker: Option<ReactionPicker> = None;
+    let mut checks_list_state = ListState::default();
+
+    // Keys of the current screen or popup, opened with ?
+    let mut help_view: Option<HelpView> = None;
+    // The home screen lists the keys of the tabs, the keymap does not change while the app runs
+    let home_keys = HelpView::for_screen(&keymap, &[KeyContext::List, KeyContext::Global], &[Action::SaveSearch]).entries;
+    let help_keys = keymap.keys_for(&[KeyContext::Global], Action::Help);
+
+    // Ad-hoc search results and the queries typed so far, most recent last
+    let mut search_tab: Option<IssueTab> = None;
+    let mut search_query = String::new();
+    let mut search_history: Vec<String> = vec![];
+    let mut history_index: Option<usize> = None;
+
+    // Filter stack applied to every tab, and the popup listing it for removal
+    let mut filters: Vec<Filter> = vec![];
+    let mut filter_popup_open = false;
+    let mut filter_list_state = ListState::default();
+    apply_filters(&filters, &config.staleness, &mut issue_tabs, &mut search_tab);
+    apply_priorities(&priorities, &mut issue_tabs, &mut search_tab);
+
+    // Fuzzy filter of the current tab while it is being typed
+    let mut fuzzy_input: Option<TextInput> = None;
+
+    // Text input popup and what its content will be used for
+    let mut text_input: Option<(InputTarget, TextInput)> = None;
+
+    // Message shown in a popup until the next key press
+    let mut status_message: Option<String> = None;
 
     loop {
+        let menu_titles = menu_titles(&config, search_tab.is_some());
         terminal.draw(|rect| {
             let size = rect.size();
             let chunks = Layout::default() // define the Menu
@@ -166,12 +396,12 @@ async fn main() -> Result<(), Box<dyn Error>> {
             let copyright = Paragraph::new(
                 "Github Assistant - All rights reserved - V0.1.5 - Simon-Busch ®"
             )
-                .style(Style::default().fg(Color::LightCyan))
+                .style(Style::default().fg(theme.accent))
                 .alignment(Alignment::Center)
                 .block(
                     Block::default()
                         .borders(Borders::ALL)
-                        .style(Style::default().fg(Color::White))
+                        .style(Style::default().fg(theme.text))
                         .title("Copyright")
                         .border_type(BorderType::Plain)
                         .border_type(BorderType::Rounded)
@@ -180,404 +410,1209 @@ async fn main() -> Result<(), Box<dyn Error>> {
             let menu = menu_titles
                 .iter()
                 .map(|t| {
-                    let (first, rest) = t.split_at(1);
+                    let (first, rest) = t.split_at(t.chars().next().map(|c| c.len_utf8()).unwrap_or(0));
                     Spans::from(
                         vec![
                             Span::styled(
                                 first,
                                 Style::default()
-                                    .fg(Color::LightCyan)
+                                    .fg(theme.accent)
                                     .add_modifier(Modifier::UNDERLINED)
                             ),
-                            Span::styled(rest, Style::default().fg(Color::White))
+                            Span::styled(rest, Style::default().fg(theme.text))
                         ]
                     )
                 })
                 .collect();
 
             let tabs = Tabs::new(menu)
-                .select(active_menu_item.into())
+                .select(menu_index(active_menu_item, &config))
                 .block(
                     Block::default()
                         .title("Menu")
                         .borders(Borders::ALL)
                         .border_type(BorderType::Rounded)
                 )
-                .style(Style::default().fg(Color::White))
-                .highlight_style(Style::default().fg(Color::LightCyan))
+                .style(Style::default().fg(theme.text))
+                .highlight_style(Style::default().fg(theme.accent))
                 .divider(Span::raw("|"));
 
             rect.render_widget(tabs, chunks[0]);
-            match active_menu_item {
-                MenuItem::Home =>
-                    rect.render_widget(
-                        render_home(
-                            &issues_list_open_len,
-                            &issues_list_closed_len,
-                            &assigned_pr_list_len,
-                            &username
-                        ),
-                        chunks[1]
-                    ),
-                MenuItem::Assignments => {
-                    if issues_list_open_len == 0 {
-                        render_error(rect, "No assigned issues found");
-                    } else {
-                        let data_chunck = Layout::default()
-                            .direction(Direction::Horizontal)
-                            .constraints(
-                                [Constraint::Percentage(30), Constraint::Percentage(70)].as_ref()
-                            )
-                            .split(chunks[1]);
-
-                        if active_open == true && show_comment == false {
-                            let selected_issue_index = issue_list_state_open.selected();
-                            let (left, right) = render_issues(
-                                &issues_list_open,
-                                selected_issue_index,
-                                show_comment
-                            );
-                            rect.render_stateful_widget(
-                                left,
-                                data_chunck[0],
-                                &mut issue_list_state_open
-                            );
-                            rect.render_widget(right, data_chunck[1]);
-                            if
-                                prompt_open == true &&
-                                show_org_modal == false &&
-                                show_repo_modal == false
-                            {
-                                render_popup(rect, [].to_vec(), "Actions".to_string(), None);
-                            } else if
-                                prompt_open == true &&
-                                show_org_modal == true &&
-                                show_repo_modal == false
-                            {
-                                render_popup(
-                                    rect,
-                                    org_list.clone(),
-                                    "Choose an organisation".to_string(),
-                                    org_or_repo_list.selected()
-                                );
-                            } else if
-                                prompt_open == true &&
-                                show_repo_modal == true &&
-                                show_org_modal == false
-                            {
-                                render_popup(
-                                    rect,
-                                    repo_list.clone(),
-                                    "Choose a repository".to_string(),
-                                    org_or_repo_list.selected()
+            if let Some(view) = diff_view.as_mut() {
+                render_diff(rect, chunks[1], view, &keymap, &theme);
+                if review_popup_open {
+                    render_popup(
+                        rect,
+                        REVIEW_EVENTS.iter()
+                            .map(|(label, _)| label.to_string())
+                            .collect(),
+                        "Submit review".to_string(),
+                        review_event_state.selected(),
+                        &theme
+                    );
+                }
+            } else {
+                match active_menu_item {
+                    MenuItem::Home => {
+                        let count = |menu_item: MenuItem| menu_item
+                            .tab_index()
+                            .and_then(|i| issue_tabs.get(i))
+                            .map(|tab| tab.total_count)
+                            .unwrap_or(0);
+                        rect.render_widget(
+                            render_home(
+                                &count(MenuItem::Assignments),
+                                &count(MenuItem::Closed),
+                                &count(MenuItem::ToReview),
+                                &count(MenuItem::Authored),
+                                &count(MenuItem::Mentions),
+                                &(inbox.unread_count() as i32),
+                                &username,
+                                &home_keys,
+                                &help_keys,
+                                &theme
+                            ),
+                            chunks[1]
+                        )
+                    }
+                    MenuItem::Inbox => render_inbox(rect, chunks[1], &inbox, &keymap, &theme),
+                    menu_item => {
+                        if let Some(tab) = current_tab(menu_item, &mut issue_tabs, &mut search_tab) {
+                            if tab.items.is_empty() {
+                                render_error(rect, tab.error.as_deref().unwrap_or(menu_item.empty_message()), &theme);
+                            } else {
+                                let data_chunck = Layout::default()
+                                    .direction(Direction::Horizontal)
+                                    .constraints(
+                                        [Constraint::Percentage(30), Constraint::Percentage(70)].as_ref()
+                                    )
+                                    .split(chunks[1]);
+
+                                let (left, right) = render_issues(
+                                    tab,
+                                    show_comment,
+                                    fuzzy_input.is_some(),
+                                    &username,
+                                    &mut details_pane,
+                                    data_chunck[1],
+                                    &config.comments,
+                                    &keymap,
+                                    &theme
                                 );
+                                rect.render_stateful_widget(left, data_chunck[0], &mut tab.state);
+                                rect.render_widget(right, data_chunck[1]);
                             }
-                        } else if active_open == true && show_comment == true {
-                            let selected_issue_index = issue_list_state_open.selected();
-                            let (left, right) = render_issues(
-                                &issues_list_open,
-                                selected_issue_index,
-                                show_comment
-                            );
-                            rect.render_stateful_widget(
-                                left,
-                                data_chunck[0],
-                                &mut issue_list_state_open
-                            );
-                            rect.render_widget(right, data_chunck[1]);
                         }
-                    }
-                }
-                MenuItem::Closed => {
-                    if issues_list_closed_len == 0 {
-                        render_error(rect, "No closed issues found");
-                    } else {
-                        let data_chunck = Layout::default()
-                            .direction(Direction::Horizontal)
-                            .constraints(
-                                [Constraint::Percentage(30), Constraint::Percentage(70)].as_ref()
-                            )
-                            .split(chunks[1]);
-                        if active_open == false {
-                            let selected_issue_index = issue_list_state_closed.selected();
-                            let (left, right) = render_issues(
-                                &issues_list_closed,
-                                selected_issue_index,
-                                show_comment
+                        if
+                            prompt_open &&
+                            !show_org_modal &&
+                            !show_repo_modal &&
+                            !show_label_modal
+                        {
+                            render_popup(rect, [].to_vec(), "Actions".to_string(), None, &theme);
+                        } else if
+                            prompt_open &&
+                            show_org_modal &&
+                            !show_repo_modal
+                        {
+                            render_popup(
+                                rect,
+                                org_list.clone(),
+                                "Choose an organisation".to_string(),
+                                org_or_repo_list.selected(),
+                                &theme
+                            );
+                        } else if
+                            prompt_open &&
+                            show_repo_modal &&
+                            !show_org_modal
+                        {
+                            render_popup(
+                                rect,
+                                repo_list.clone(),
+                                "Choose a repository".to_string(),
+                                org_or_repo_list.selected(),
+                                &theme
                             );
-                            rect.render_stateful_widget(
-                                left,
-                                data_chunck[0],
-                                &mut issue_list_state_closed
+                        } else if prompt_open && show_label_modal {
+                            render_popup(
+                                rect,
+                                label_list.iter().map(|(name, count)| format!("{} ({})", name, count)).collect(),
+                                "Choose a label".to_string(),
+                                org_or_repo_list.selected(),
+                                &theme
                             );
-                            rect.render_widget(right, data_chunck[1]);
                         }
                     }
                 }
-                MenuItem::Refresh => {}
-                MenuItem::ToReview => {
-                    if assigned_pr_list_len == 0 {
-                        render_error(rect, "No Assigned PR");
-                        // Wait for 5 seconds
-                        //TODO
-                        // std::thread::sleep(std::time::Duration::from_secs(5));
-                        // Set the active menu item to Home
-                        active_menu_item = MenuItem::Home;
-                    } else {
-                        let data_chunck = Layout::default()
-                            .direction(Direction::Horizontal)
-                            .constraints(
-                                [Constraint::Percentage(30), Constraint::Percentage(70)].as_ref()
-                            )
-                            .split(chunks[1]);
-                        let selected_issue_index = issue_list_state_to_review.selected();
-                        let (left, right) = render_issues(
-                            &assigned_pr_list,
-                            selected_issue_index,
-                            show_comment
-                        );
-                        rect.render_stateful_widget(
-                            left,
-                            data_chunck[0],
-                            &mut issue_list_state_to_review
-                        );
-                        rect.render_widget(right, data_chunck[1]);
-                    }
-                }
             }
             rect.render_widget(copyright, chunks[2]);
+            if filter_popup_open {
+                render_popup(
+                    rect,
+                    filters.iter().map(|filter| filter.label()).collect(),
+                    format!(
+                        "Active filters - {}",
+                        keymap.hint(&[KeyContext::Global], &[(Action::Confirm, "remove"), (Action::Cancel, "close")])
+                    ),
+                    filter_list_state.selected(),
+                    &theme
+                );
+            }
+            if let Some(item) = &checks_item {
+                render_checks(rect, item, &mut checks_list_state, &keymap, &theme);
+            }
+            if let Some(view) = timeline_view.as_mut() {
+                render_timeline(rect, view, &keymap, &theme);
+            }
+            if let Some(picker) = reaction_picker.as_mut() {
+                render_reactions(rect, picker, &keymap, &theme);
+            }
+            if let Some(form) = &merge_form {
+                render_merge(rect, form, &theme);
+            }
+            if let Some((target, input)) = &text_input {
+                render_input(rect, &target.title(), input, &theme);
+            }
+            if let Some(view) = &help_view {
+                render_help(rect, view, &keymap, &theme);
+            }
+            if let Some(message) = &status_message {
+                render_error(rect, message, &theme);
+            }
         })?;
 
-        match rx.recv()? {
+        let event = rx.recv()?;
+        // Keys typed in the inputs and the merge form are text, the others go through the keymap
+        let action = match event {
+            Event::Input(key) if
+                status_message.is_none() &&
+                text_input.is_none() &&
+                fuzzy_input.is_none() &&
+                merge_form.is_none()
+            => keymap.resolve(&key_contexts(active_menu_item, diff_view.is_some()), Key::from_event(key)),
+            _ => None,
+        };
+        match event {
             Event::Input(event) =>
-                match (event.code, event.modifiers) {
-                    (KeyCode::Char('q'), _) => {
+                match (action, event.code, event.modifiers) {
+                    _ if status_message.is_some() => {
+                        status_message = None;
+                    }
+                    (_, KeyCode::Esc, _) if text_input.is_some() => {
+                        text_input = None;
+                    }
+                    (_, KeyCode::Up, _) if
+                        matches!(text_input, Some((InputTarget::SearchQuery, _))) &&
+                        !search_history.is_empty()
+                    => {
+                        let index = history_index
+                            .map(|i| i.saturating_sub(1))
+                            .unwrap_or(search_history.len() - 1);
+                        history_index = Some(index);
+                        text_input = Some((InputTarget::SearchQuery, TextInput::new(&search_history[index])));
+                    }
+                    (_, KeyCode::Down, _) if matches!(text_input, Some((InputTarget::SearchQuery, _))) => {
+                        match history_index {
+                            Some(index) if index + 1 < search_history.len() => {
+                                history_index = Some(index + 1);
+                                text_input = Some((InputTarget::SearchQuery, TextInput::new(&search_history[index + 1])));
+                            }
+                            _ => {
+                                history_index = None;
+                                text_input = Some((InputTarget::SearchQuery, TextInput::default()));
+                            }
+                        }
+                    }
+                    (_, KeyCode::Enter, _) if matches!(text_input, Some((InputTarget::SearchQuery, _))) => {
+                        if let Some((_, input)) = text_input.take() {
+                            let query = input.value.trim().to_string();
+                            if !query.is_empty() {
+                                search_history.retain(|previous| *previous != query);
+                                search_history.push(query.clone());
+                                let search = SavedSearch { name: format!("Search: {}", query), query };
+                                match fetch_search_tab(&search, &access_token).await {
+                                    Ok(mut tab) => {
+                                        tab.set_filters(&filters, &config.staleness);
+                                        tab.set_priorities(&priorities);
+                                        search_tab = Some(tab);
+                                        search_query = search.query;
+                                        diff_view = None;
+                                        active_menu_item = MenuItem::Search;
+                                    }
+                                    Err(e) => {
+                                        status_message = Some(e.to_string());
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    (_, KeyCode::Enter, _) if matches!(text_input, Some((InputTarget::Filters, _))) => {
+                        if let Some((_, input)) = text_input.take() {
+                            for token in input.value.split_whitespace() {
+                                match Filter::parse(token) {
+                                    Ok(filter) => push_filter(&mut filters, filter),
+                                    Err(e) => {
+                                        status_message = Some(e);
+                                    }
+                                }
+                            }
+                            apply_filters(&filters, &config.staleness, &mut issue_tabs, &mut search_tab);
+                        }
+                    }
+                    (_, KeyCode::Enter, _) if matches!(text_input, Some((InputTarget::SaveSearch(_), _))) => {
+                        if let Some((InputTarget::SaveSearch(query), input)) = text_input.take() {
+                            let name = input.value.trim().to_string();
+                            if !name.is_empty() {
+                                let search = SavedSearch { name: name.clone(), query };
+                                match Config::save_search(&search) {
+                                    Ok(()) => {
+                                        config.tabs.push(search);
+                                        // The saved tabs follow the built-in ones in issue_tabs, in config order
+                                        if let Some(mut tab) = search_tab.take() {
+                                            tab.title = name;
+                                            issue_tabs.push(tab);
+                                        }
+                                        active_menu_item = MenuItem::Custom(config.tabs.len() - 1);
+                                    }
+                                    Err(e) => {
+                                        status_message = Some(format!("The search was not saved: {}", e));
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    (_, KeyCode::Enter, modifiers) if
+                        text_input.is_some() &&
+                        !modifiers.contains(KeyModifiers::ALT)
+                    => {
+                        if let (Some((target, input)), Some(view)) = (text_input.take(), diff_view.as_mut()) {
+                            let repo_owner = view.item.organization.clone().unwrap_or_default();
+                            let repo_name = view.item.repository.clone().unwrap_or_default();
+                            match target {
+                                InputTarget::ReviewComment(mut draft) => {
+                                    if !input.value.trim().is_empty() {
+                                        draft.body = input.value;
+                                        match create_review_comment(
+                                            &repo_owner,
+                                            &repo_name,
+                                            view.item.number,
+                                            &view.pull_request.head.sha,
+                                            &draft,
+                                            &access_token
+                                        ).await {
+                                            Ok(comment) => view.review_comments.push(comment),
+                                            Err(e) => {
+                                                status_message = Some(e.to_string());
+                                            }
+                                        }
+                                    }
+                                }
+                                InputTarget::PendingReviewComment(mut draft) => {
+                                    if !input.value.trim().is_empty() {
+                                        draft.body = input.value;
+                                        view.pending_comments.push(draft);
+                                    }
+                                }
+                                InputTarget::ReviewSummary(event) => {
+                                    match submit_review(
+                                        &repo_owner,
+                                        &repo_name,
+                                        view.item.number,
+                                        &view.pull_request.head.sha,
+                                        event,
+                                        &input.value,
+                                        &view.pending_comments,
+                                        &access_token
+                                    ).await {
+                                        Ok(()) => {
+                                            view.pending_comments.clear();
+                                            if let Ok(comments) = fetch_review_comments(
+                                                &repo_owner,
+                                                &repo_name,
+                                                view.item.number,
+                                                &access_token
+                                            ).await {
+                                                view.review_comments = comments;
+                                            }
+                                            status_message = Some("Review submitted".to_string());
+                                        }
+                                        Err(e) => {
+                                            status_message = Some(e.to_string());
+                                        }
+                                    }
+                                }
+                                InputTarget::SearchQuery | InputTarget::SaveSearch(_) | InputTarget::Filters => {}
+                            }
+                        }
+                    }
+                    (_, code, modifiers) if text_input.is_some() => {
+                        if let Some((_, input)) = text_input.as_mut() {
+                            input.handle_key(code, modifiers);
+                        }
+                    }
+                    (_, KeyCode::Esc, _) if fuzzy_input.is_some() => {
+                        fuzzy_input = None;
+                        if let Some(tab) = current_tab(active_menu_item, &mut issue_tabs, &mut search_tab) {
+                            tab.set_fuzzy("");
+                        }
+                    }
+                    (_, KeyCode::Enter, _) if fuzzy_input.is_some() => {
+                        fuzzy_input = None;
+                    }
+                    (_, KeyCode::Down, _) if fuzzy_input.is_some() => {
+                        if let Some(tab) = current_tab(active_menu_item, &mut issue_tabs, &mut search_tab) {
+                            tab.move_selection(1);
+                        }
+                    }
+                    (_, KeyCode::Up, _) if fuzzy_input.is_some() => {
+                        if let Some(tab) = current_tab(active_menu_item, &mut issue_tabs, &mut search_tab) {
+                            tab.move_selection(-1);
+                        }
+                    }
+                    (_, code, modifiers) if fuzzy_input.is_some() => {
+                        if let (Some(input), Some(tab)) = (
+                            fuzzy_input.as_mut(),
+                            current_tab(active_menu_item, &mut issue_tabs, &mut search_tab),
+                        ) {
+                            if input.handle_key(code, modifiers) {
+                                tab.set_fuzzy(&input.value);
+                            }
+                        }
+                    }
+                    (_, KeyCode::Esc, _) if merge_form.is_some() => {
+                        merge_form = None;
+                    }
+                    (_, KeyCode::Enter, modifiers) if
+                        merge_form.is_some() &&
+                        !modifiers.contains(KeyModifiers::ALT)
+                    => {
+                        if let Some(form) = merge_form.take() {
+                            let repo_owner = form.item.organization.clone().unwrap_or_default();
+                            let repo_name = form.item.repository.clone().unwrap_or_default();
+                            match merge_pull_request(
+                                &repo_owner,
+                                &repo_name,
+                                form.pull_request.number,
+                                &form.pull_request.head.sha,
+                                form.method(),
+                                &form.title.value,
+                                &form.message.value,
+                                &access_token
+                            ).await {
+                                Ok(()) => {
+                                    let mut message = format!("Pull request #{} merged", form.pull_request.number);
+                                    if form.delete_branch {
+                                        match delete_branch(
+                                            &repo_owner,
+                                            &repo_name,
+                                            &form.pull_request.head.branch,
+                                            &access_token
+                                        ).await {
+                                            Ok(()) => message.push_str(", branch deleted"),
+                                            Err(e) => message = format!("{}, but: {}", message, e),
+                                        }
+                                    }
+                                    for tab in issue_tabs.iter_mut().chain(search_tab.iter_mut()) {
+                                        tab.remove_item(&form.item.url);
+                                    }
+                                    diff_view = None;
+                                    status_message = Some(message);
+                                }
+                                Err(e) => {
+                                    // Keep the form so the merge can be retried after reading the error
+                                    status_message = Some(e.to_string());
+                                    merge_form = Some(form);
+                                }
+                            }
+                        }
+                    }
+                    (_, code, modifiers) if merge_form.is_some() => {
+                        if let Some(form) = merge_form.as_mut() {
+                            form.handle_key(code, modifiers);
+                        }
+                    }
+                    (Some(Action::Cancel | Action::Help), ..) if help_view.is_some() => {
+                        help_view = None;
+                    }
+                    (Some(Action::MoveDown), ..) if help_view.is_some() => {
+                        if let Some(view) = help_view.as_mut() {
+                            view.scroll_by(1);
+                        }
+                    }
+                    (Some(Action::MoveUp), ..) if help_view.is_some() => {
+                        if let Some(view) = help_view.as_mut() {
+                            view.scroll_by(-1);
+                        }
+                    }
+                    (Some(Action::PageDown), ..) if help_view.is_some() => {
+                        if let Some(view) = help_view.as_mut() {
+                            view.scroll_by(10);
+                        }
+                    }
+                    (Some(Action::PageUp), ..) if help_view.is_some() => {
+                        if let Some(view) = help_view.as_mut() {
+                            view.scroll_by(-10);
+                        }
+                    }
+                    _ if help_view.is_some() => {}
+                    (Some(Action::Help), ..) => {
+                        // The popup on top takes the keys, so its keys are shown rather than those of the screen
+                        let popup = if checks_item.is_some() {
+                            Some(Popup::Checks)
+                        } else if timeline_view.is_some() {
+                            Some(Popup::Timeline)
+                        } else if reaction_picker.is_some() {
+                            Some(Popup::Reactions)
+                        } else if review_popup_open {
+                            Some(Popup::Review)
+                        } else if filter_popup_open {
+                            Some(Popup::Filters)
+                        } else if prompt_open && (show_org_modal || show_repo_modal || show_label_modal) {
+                            Some(Popup::Picker)
+                        } else if prompt_open {
+                            Some(Popup::Actions { close_issue: matches!(active_menu_item, MenuItem::Assignments) })
+                        } else {
+                            None
+                        };
+                        let view = match popup {
+                            Some(popup) => HelpView::for_popup(&keymap, popup),
+                            None => {
+                                let hidden: &[Action] = if matches!(active_menu_item, MenuItem::Search) { &[] } else { &[Action::SaveSearch] };
+                                HelpView::for_screen(&keymap, &key_contexts(active_menu_item, diff_view.is_some()), hidden)
+                            }
+                        };
+                        help_view = Some(view);
+                    }
+                    (Some(Action::Cancel), ..) if checks_item.is_some() => {
+                        checks_item = None;
+                    }
+                    (Some(Action::MoveDown), ..) if checks_item.is_some() => {
+                        if let Some(ci) = checks_item.as_ref().and_then(|item| item.ci_status.as_ref()) {
+                            move_selection(&mut checks_list_state, &ci.checks, 1);
+                        }
+                    }
+                    (Some(Action::MoveUp), ..) if checks_item.is_some() => {
+                        if let Some(ci) = checks_item.as_ref().and_then(|item| item.ci_status.as_ref()) {
+                            move_selection(&mut checks_list_state, &ci.checks, -1);
+                        }
+                    }
+                    (Some(Action::Confirm), ..) if checks_item.is_some() => {
+                        let url = checks_item
+                            .as_ref()
+                            .and_then(|item| item.ci_status.as_ref())
+                            .and_then(|ci| checks_list_state.selected().and_then(|i| ci.checks.get(i)))
+                            .and_then(|check| check.url.clone());
+                        match url {
+                            Some(url) => {
+                                if let Err(e) = open::that(&url) {
+                                    status_message = Some(format!("Failed to open URL \'{}\': {}", url, e));
+                                }
+                            }
+                            None => {
+                                status_message = Some("This check has no log URL".to_string());
+                            }
+                        }
+                    }
+                    _ if checks_item.is_some() => {}
+                    (Some(Action::Cancel), ..) if timeline_view.is_some() => {
+                        timeline_view = None;
+                    }
+                    (Some(Action::MoveDown), ..) if timeline_view.is_some() => {
+                        if let Some(view) = timeline_view.as_mut() {
+                            move_selection(&mut view.state, &view.events, 1);
+                        }
+                    }
+                    (Some(Action::MoveUp), ..) if timeline_view.is_some() => {
+                        if let Some(view) = timeline_view.as_mut() {
+                            move_selection(&mut view.state, &view.events, -1);
+                        }
+                    }
+                    (Some(Action::Confirm), ..) if timeline_view.is_some() => {
+                        let link = timeline_view
+                            .as_ref()
+                            .and_then(|view| view.selected())
+                            .and_then(|event| event.link())
+                            .map(|link| link.to_string());
+                        match link {
+                            Some(link) => {
+                                // Jump to the linked item when one of the tabs lists it, the browser otherwise
+                                let mut target = None;
+                                for (index, tab) in issue_tabs.iter_mut().enumerate() {
+                                    if tab.select_url(&link) {
+                                        target = Some(MenuItem::from_tab_index(index));
+                                        break;
+                                    }
+                                }
+                                if target.is_none() && search_tab.as_mut().map(|tab| tab.select_url(&link)).unwrap_or(false) {
+                                    target = Some(MenuItem::Search);
+                                }
+                                match target {
+                                    Some(menu_item) => {
+                                        active_menu_item = menu_item;
+                                        timeline_view = None;
+                                        diff_view = None;
+                                    }
+                                    None => {
+                                        if let Err(e) = open::that(&link) {
+                                            status_message = Some(format!("Failed to open URL \'{}\': {}", link, e));
+                                        }
+                                    }
+                                }
+                            }
+                            None => {
+                                status_message = Some("Nothing is linked to this event".to_string());
+                            }
+                        }
+                    }
+                    _ if timeline_view.is_some() => {}
+                    (Some(Action::Cancel), ..) if reaction_picker.is_some() => {
+                        reaction_picker = None;
+                    }
+                    (Some(Action::MoveDown), ..) if reaction_picker.is_some() => {
+                        if let Some(picker) = reaction_picker.as_mut() {
+                            picker.move_target(1);
+                        }
+                    }
+                    (Some(Action::MoveUp), ..) if reaction_picker.is_some() => {
+                        if let Some(picker) = reaction_picker.as_mut() {
+                            picker.move_target(-1);
+                        }
+                    }
+                    (Some(Action::MoveRight), ..) if reaction_picker.is_some() => {
+                        if let Some(picker) = reaction_picker.as_mut() {
+                            picker.move_emoji(1);
+                        }
+                    }
+                    (Some(Action::MoveLeft), ..) if reaction_picker.is_some() => {
+                        if let Some(picker) = reaction_picker.as_mut() {
+                            picker.move_emoji(-1);
+                        }
+                    }
+                    (Some(Action::Confirm), ..) if reaction_picker.is_some() => {
+                        if let Some(picker) = reaction_picker.as_mut() {
+                            let item = &picker.item;
+                            let comment_id = picker.comment_id();
+                            let content = picker.content();
+                            match add_reaction(
+                                item.organization.as_deref().unwrap_or_default(),
+                                item.repository.as_deref().unwrap_or_default(),
+                                item.number,
+                                comment_id,
+                                content,
+                                &access_token
+                            ).await {
+                                Ok(true) => {
+                                    for list_item in issue_tabs
+                                        .iter_mut()
+                                        .chain(search_tab.iter_mut())
+                                        .flat_map(|tab| tab.items.iter_mut())
+                                        .filter(|i| i.url == picker.item.url)
+                                    {
+                                        list_item.add_reaction(comment_id, content);
+                                    }
+                                    picker.item.add_reaction(comment_id, content);
+                                }
+                                Ok(false) => {
+                                    status_message = Some(format!("You already reacted with {}", REACTION_KINDS[picker.emoji].1));
+                                }
+                                Err(e) => {
+                                    status_message = Some(e.to_string());
+                                }
+                            }
+                        }
+                    }
+                    _ if reaction_picker.is_some() => {}
+                    (Some(Action::Quit), ..) => {
                         disable_raw_mode()?;
                         terminal.show_cursor()?;
                         break;
                     }
-                    (KeyCode::Char('h'), KeyModifiers::CONTROL) => {
+                    (Some(Action::Home), ..) => {
+                        diff_view = None;
                         active_menu_item = MenuItem::Home;
                     }
-                    (KeyCode::Char('a'), KeyModifiers::CONTROL) => {
-                        active_open = true;
-                        to_review_open = false;
+                    (Some(Action::Assignments), ..) => {
+                        diff_view = None;
                         active_menu_item = MenuItem::Assignments;
                     }
-                    (KeyCode::Char('c'), KeyModifiers::CONTROL) => {
-                        active_open = false;
-                        to_review_open = false;
+                    (Some(Action::Closed), ..) => {
+                        diff_view = None;
                         active_menu_item = MenuItem::Closed;
                     }
-                    (KeyCode::Down, _) => {
-                        if show_org_modal == true {
+                    (Some(Action::Cancel), ..) if review_popup_open => {
+                        review_popup_open = false;
+                    }
+                    (Some(Action::MoveDown), ..) if review_popup_open => {
+                        move_selection(&mut review_event_state, &REVIEW_EVENTS, 1);
+                    }
+                    (Some(Action::MoveUp), ..) if review_popup_open => {
+                        move_selection(&mut review_event_state, &REVIEW_EVENTS, -1);
+                    }
+                    (Some(Action::Confirm), ..) if review_popup_open => {
+                        review_popup_open = false;
+                        let (_, event) = REVIEW_EVENTS[review_event_state.selected().unwrap_or(0)];
+                        text_input = Some((InputTarget::ReviewSummary(event), TextInput::multiline()));
+                    }
+                    (Some(Action::Merge), ..) if !matches!(active_menu_item, MenuItem::Home) => {
+                        let selected_item = match &diff_view {
+                            Some(view) => Some(view.item.clone()),
+                            None => current_tab(active_menu_item, &mut issue_tabs, &mut search_tab)
+                                .and_then(|tab| tab.selected_item().cloned()),
+                        };
+                        if let Some(item) = selected_item {
+                            if !item.is_pr {
+                                status_message = Some("The selected item is not a pull request".to_string());
+                            } else {
+                                let repo_owner = item.organization.clone().unwrap_or_default();
+                                let repo_name = item.repository.clone().unwrap_or_default();
+                                match fetch_mergeable_pull_request(&repo_owner, &repo_name, item.number, &access_token).await {
+                                    Ok(pull_request) => {
+                                        let (message, blocking) = pull_request.mergeability();
+                                        if blocking {
+                                            status_message = Some(message);
+                                        } else {
+                                            merge_form = Some(MergeForm::new(item, pull_request));
+                                        }
+                                    }
+                                    Err(e) => {
+                                        status_message = Some(e.to_string());
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    (Some(Action::Checks), ..) if !matches!(active_menu_item, MenuItem::Home) => {
+                        let selected_item = match &diff_view {
+                            Some(view) => Some(view.item.clone()),
+                            None => current_tab(active_menu_item, &mut issue_tabs, &mut search_tab)
+                                .and_then(|tab| tab.selected_item().cloned()),
+                        };
+                        if let Some(mut item) = selected_item {
+                            if !item.is_pr {
+                                status_message = Some("The selected item is not a pull request".to_string());
+                            } else {
+                                // Refresh the checks, they may have moved on since the lists were loaded
+                                let repo_owner = item.organization.clone().unwrap_or_default();
+                                let repo_name = item.repository.clone().unwrap_or_default();
+                                match fetch_pull_request(&repo_owner, &repo_name, item.number, &access_token).await {
+                                    Ok(pull_request) => {
+                                        match fetch_ci_status(&repo_owner, &repo_name, &pull_request.head.sha, &access_token).await {
+                                            Ok(ci) => {
+                                                for list_item in issue_tabs
+                                                    .iter_mut()
+                                                    .chain(search_tab.iter_mut())
+                                                    .flat_map(|tab| tab.items.iter_mut())
+                                                    .filter(|i| i.url == item.url)
+                                                {
+                                                    list_item.ci_status = Some(ci.clone());
+                                                }
+                                                item.ci_status = Some(ci);
+                                            }
+                                            Err(e) => {
+                                                status_message = Some(e.to_string());
+                                            }
+                                        }
+                                    }
+                                    Err(e) => {
+                                        status_message = Some(e.to_string());
+                                    }
+                                }
+                                checks_list_state.select(Some(0));
+                                checks_item = Some(item);
+                            }
+                        }
+                    }
+                    (Some(Action::React), ..) if diff_view.is_none() => {
+                        if let Some(item) = current_tab(active_menu_item, &mut issue_tabs, &mut search_tab)
+                            .and_then(|tab| tab.selected_item())
+                        {
+                            reaction_picker = Some(ReactionPicker::new(item.clone()));
+                        }
+                    }
+                    (Some(Action::Timeline), ..) if diff_view.is_none() => {
+                        let selected_item = current_tab(active_menu_item, &mut issue_tabs, &mut search_tab)
+                            .and_then(|tab| tab.selected_item().cloned());
+                        if let Some(item) = selected_item {
+                            let repo_owner = item.organization.clone().unwrap_or_default();
+                            let repo_name = item.repository.clone().unwrap_or_default();
+                            match fetch_timeline(&repo_owner, &repo_name, item.number, &access_token).await {
+                                Ok(events) => {
+                                    timeline_view = Some(TimelineView::new(item, events));
+                                }
+                                Err(e) => {
+                                    status_message = Some(e.to_string());
+                                }
+                            }
+                        }
+                    }
+                    (Some(Action::Cancel), ..) if diff_view.is_some() => {
+                        diff_view = None;
+                    }
+                    // Moves and review actions of the diff, the global ones like tab switches fall through
+                    (Some(action), ..) if
+                        diff_view.is_some() &&
+                        matches!(
+                            action,
+                            Action::MoveLeft | Action::MoveRight | Action::MoveUp | Action::MoveDown |
+                            Action::PageUp | Action::PageDown | Action::Top | Action::Bottom | Action::Confirm |
+                            Action::ExpandFile | Action::Comment | Action::AddToReview | Action::SubmitReview
+                        )
+                    => {
+                        if let Some(view) = diff_view.as_mut() {
+                            let line_count = view.selected_lines().len() as isize;
+                            match action {
+                                Action::MoveLeft => {
+                                    view.focus_patch = false;
+                                }
+                                Action::MoveRight => {
+                                    view.focus_patch = true;
+                                }
+                                Action::MoveDown if view.focus_patch => view.move_cursor(1),
+                                Action::MoveUp if view.focus_patch => view.move_cursor(-1),
+                                // Pages always move in the patch, the file list is short enough to walk
+                                Action::PageDown => view.move_cursor(20),
+                                Action::PageUp => view.move_cursor(-20),
+                                Action::Top if view.focus_patch => view.move_cursor(-line_count),
+                                Action::Bottom if view.focus_patch => view.move_cursor(line_count),
+                                Action::MoveDown => {
+                                    move_selection(&mut view.file_list_state, &view.files, 1);
+                                    view.reset_cursor();
+                                }
+                                Action::MoveUp => {
+                                    move_selection(&mut view.file_list_state, &view.files, -1);
+                                    view.reset_cursor();
+                                }
+                                Action::Top | Action::Bottom => {
+                                    let delta = view.files.len() as isize;
+                                    move_selection(&mut view.file_list_state, &view.files, if action == Action::Top { -delta } else { delta });
+                                    view.reset_cursor();
+                                }
+                                Action::ExpandFile => view.toggle_expanded(),
+                                Action::Comment | Action::AddToReview => {
+                                    match view.comment_anchor() {
+                                        Some((path, line, side)) if view.focus_patch => {
+                                            let draft = DraftReviewComment { path, line, side, body: String::new() };
+                                            let target = if action == Action::Comment {
+                                                InputTarget::ReviewComment(draft)
+                                            } else {
+                                                InputTarget::PendingReviewComment(draft)
+                                            };
+                                            text_input = Some((target, TextInput::multiline()));
+                                        }
+                                        _ => {
+                                            status_message = Some(format!(
+                                                "Press {} and select a changed or context line to comment on",
+                                                keymap.keys_for(&[KeyContext::Diff, KeyContext::Global], Action::MoveRight)
+                                            ));
+                                        }
+                                    }
+                                }
+                                Action::SubmitReview => {
+                                    review_event_state.select(Some(0));
+                                    review_popup_open = true;
+                                }
+                                Action::Confirm => {
+                                    let url = format!("{}/files", view.item.url);
+                                    if let Err(e) = open::that(&url) {
+                                        status_message = Some(format!("Failed to open URL \'{}\': {}", url, e));
+                                    }
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                    (Some(Action::MoveDown), ..) if matches!(active_menu_item, MenuItem::Inbox) => {
+                        move_selection(&mut inbox.state, &inbox.notifications, 1);
+                    }
+                    (Some(Action::MoveUp), ..) if matches!(active_menu_item, MenuItem::Inbox) => {
+                        move_selection(&mut inbox.state, &inbox.notifications, -1);
+                    }
+                    (Some(Action::Confirm), ..) if matches!(active_menu_item, MenuItem::Inbox) => {
+                        if let Some(notification) = inbox.selected().cloned() {
+                            let url = notification.html_url();
+                            if let Err(e) = open::that(&url) {
+                                status_message = Some(format!("Failed to open URL \'{}\': {}", url, e));
+                            }
+                            if notification.unread {
+                                match mark_notification_read(&notification.id, &access_token).await {
+                                    Ok(()) => inbox.mark_read(&notification.id),
+                                    Err(e) => {
+                                        status_message = Some(e.to_string());
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    (Some(Action::MarkRead), ..) if matches!(active_menu_item, MenuItem::Inbox) => {
+                        if let Some(id) = inbox.selected().map(|n| n.id.clone()) {
+                            match mark_notification_read(&id, &access_token).await {
+                                Ok(()) => inbox.mark_read(&id),
+                                Err(e) => {
+                                    status_message = Some(e.to_string());
+                                }
+                            }
+                        }
+                    }
+                    (Some(Action::MarkDone), ..) if matches!(active_menu_item, MenuItem::Inbox) => {
+                        if let Some(id) = inbox.selected().map(|n| n.id.clone()) {
+                            match mark_notification_done(&id, &access_token).await {
+                                Ok(()) => inbox.remove(&id),
+                                Err(e) => {
+                                    status_message = Some(e.to_string());
+                                }
+                            }
+                        }
+                    }
+                    (Some(Action::Unsubscribe), ..) if matches!(active_menu_item, MenuItem::Inbox) => {
+                        if let Some(notification) = inbox.selected().cloned() {
+                            match unsubscribe_notification(&notification.id, &access_token).await {
+                                Ok(()) => {
+                                    status_message = Some(format!("Unsubscribed from {}", notification.subject.title));
+                                }
+                                Err(e) => {
+                                    status_message = Some(e.to_string());
+                                }
+                            }
+                        }
+                    }
+                    (Some(Action::Cancel), ..) if filter_popup_open => {
+                        filter_popup_open = false;
+                    }
+                    (Some(Action::MoveDown), ..) if filter_popup_open => {
+                        move_selection(&mut filter_list_state, &filters, 1);
+                    }
+                    (Some(Action::MoveUp), ..) if filter_popup_open => {
+                        move_selection(&mut filter_list_state, &filters, -1);
+                    }
+                    (Some(Action::Confirm), ..) if filter_popup_open => {
+                        if let Some(index) = filter_list_state.selected().filter(|i| *i < filters.len()) {
+                            filters.remove(index);
+                            apply_filters(&filters, &config.staleness, &mut issue_tabs, &mut search_tab);
+                        }
+                        filter_list_state.select(Some(0));
+                        filter_popup_open = !filters.is_empty();
+                    }
+                    (Some(Action::Cancel), ..) if prompt_open => {
+                        prompt_open = false;
+                        show_org_modal = false;
+                        show_repo_modal = false;
+                        show_label_modal = false;
+                    }
+                    (Some(Action::MoveDown), ..) => {
+                        if show_org_modal {
                             let (state, items) = get_current_state_repo_org_list(
                                 &mut org_or_repo_list,
                                 &org_list
                             );
                             move_selection_org_repo(state, items, 1);
-                        } else if show_repo_modal == true {
+                        } else if show_repo_modal {
                             let (state, items) = get_current_state_repo_org_list(
                                 &mut org_or_repo_list,
                                 &repo_list
                             );
                             move_selection_org_repo(state, items, 1);
-                        } else {
-                            let (state, items) = get_current_state_and_list(
-                                active_open,
-                                to_review_open,
-                                &mut issue_list_state_open,
-                                &mut issue_list_state_closed,
-                                &mut issue_list_state_to_review,
-                                &issues_list_open,
-                                &issues_list_closed,
-                                &assigned_pr_list
-                            );
-                            move_selection(state, items, 1);
+                        } else if show_label_modal {
+                            move_selection(&mut org_or_repo_list, &label_list, 1);
+                        } else if details_pane.focused {
+                            details_pane.scroll_by(1);
+                        } else if let Some(tab) = current_tab(active_menu_item, &mut issue_tabs, &mut search_tab) {
+                            tab.move_selection(1);
                         }
                     }
-                    (KeyCode::Up, _) => {
-                        if show_org_modal == true {
+                    (Some(Action::MoveUp), ..) => {
+                        if show_org_modal {
                             let (state, items) = get_current_state_repo_org_list(
                                 &mut org_or_repo_list,
                                 &org_list
                             );
                             move_selection_org_repo(state, items, -1);
-                        } else if show_repo_modal == true {
+                        } else if show_repo_modal {
                             let (state, items) = get_current_state_repo_org_list(
                                 &mut org_or_repo_list,
                                 &repo_list
                             );
                             move_selection_org_repo(state, items, -1);
-                        } else {
-                            let (state, _) = get_current_state_and_list(
-                                active_open,
-                                to_review_open,
-                                &mut issue_list_state_open,
-                                &mut issue_list_state_closed,
-                                &mut issue_list_state_to_review,
-                                &issues_list_open,
-                                &issues_list_closed,
-                                &assigned_pr_list
-                            );
-                            move_selection(state, &issues_list_open, -1);
+                        } else if show_label_modal {
+                            move_selection(&mut org_or_repo_list, &label_list, -1);
+                        } else if details_pane.focused {
+                            details_pane.scroll_by(-1);
+                        } else if let Some(tab) = current_tab(active_menu_item, &mut issue_tabs, &mut search_tab) {
+                            tab.move_selection(-1);
                         }
                     }
-                    (KeyCode::Enter, _) => {
-                        if show_org_modal == true {
+                    (Some(Action::Confirm), ..) => {
+                        if show_org_modal {
                             let (state, items) = get_current_state_repo_org_list(
                                 &mut org_or_repo_list,
                                 &org_list
                             );
-                            issues_list_open = filter_issues_by_org(
-                                &issues_list_open,
-                                items[state.selected().unwrap()].clone()
-                            );
+                            if let Some(org) = state.selected().and_then(|i| items.get(i)) {
+                                push_filter(&mut filters, Filter::Org(org.clone()));
+                                apply_filters(&filters, &config.staleness, &mut issue_tabs, &mut search_tab);
+                            }
                             show_org_modal = false;
                             show_repo_modal = false;
                             prompt_open = false;
                             org_or_repo_list.select(Some(0));
-                        } else if show_repo_modal == true {
+                        } else if show_repo_modal {
                             let (state, items) = get_current_state_repo_org_list(
                                 &mut org_or_repo_list,
                                 &repo_list
                             );
-                            issues_list_open = filter_issues_by_repo(
-                                &issues_list_open,
-                                items[state.selected().unwrap()].clone()
-                            );
+                            if let Some(repo) = state.selected().and_then(|i| items.get(i)) {
+                                push_filter(&mut filters, Filter::Repo(repo.clone()));
+                                apply_filters(&filters, &config.staleness, &mut issue_tabs, &mut search_tab);
+                            }
                             show_org_modal = false;
                             show_repo_modal = false;
                             prompt_open = false;
                             org_or_repo_list.select(Some(0));
-                        } else {
-                            let (state, list) = get_current_state_and_list(
-                                active_open,
-                                to_review_open,
-                                &mut issue_list_state_open,
-                                &mut issue_list_state_closed,
-                                &mut issue_list_state_to_review,
-                                &issues_list_open,
-                                &issues_list_closed,
-                                &assigned_pr_list
-                            );
-                            if let Some(selected) = state.selected() {
-                                let url = &list[selected].url;
-                                if let Err(e) = open::that(url) {
-                                    eprintln!("Failed to open URL '{}': {}", url, e);
-                                }
+                        } else if show_label_modal {
+                            if let Some((label, _)) = org_or_repo_list.selected().and_then(|i| label_list.get(i)) {
+                                push_filter(&mut filters, Filter::Label(label.clone()));
+                                apply_filters(&filters, &config.staleness, &mut issue_tabs, &mut search_tab);
+                            }
+                            show_label_modal = false;
+                            prompt_open = false;
+                            org_or_repo_list.select(Some(0));
+                        } else if let Some(item) = current_tab(active_menu_item, &mut issue_tabs, &mut search_tab)
+                            .and_then(|tab| tab.selected_item())
+                        {
+                            if let Err(e) = open::that(&item.url) {
+                                status_message = Some(format!("Failed to open URL \'{}\': {}", item.url, e));
                             }
                         }
                     }
-                    (KeyCode::Right, _) => {
-                        if active_open == true {
-                            show_comment = true;
+                    (Some(Action::MoveRight), ..) => {
+                        show_comment = true;
+                        details_pane.top();
+                    }
+                    (Some(Action::MoveLeft), ..) => {
+                        show_comment = false;
+                        details_pane.top();
+                    }
+                    (Some(Action::ToggleHidden), ..) => {
+                        details_pane.show_hidden = !details_pane.show_hidden;
+                    }
+                    (Some(Action::ToggleFocus), ..) if current_tab(active_menu_item, &mut issue_tabs, &mut search_tab).is_some() => {
+                        details_pane.focused = !details_pane.focused;
+                    }
+                    (Some(Action::PageDown), ..) => {
+                        details_pane.page(1);
+                    }
+                    (Some(Action::PageUp), ..) => {
+                        details_pane.page(-1);
+                    }
+                    // First or last item of the list, unless the details have the focus
+                    (Some(Action::Top), ..) => {
+                        match current_tab(active_menu_item, &mut issue_tabs, &mut search_tab) {
+                            Some(tab) if !details_pane.focused => tab.move_selection(-(tab.rows.len() as isize)),
+                            _ => details_pane.top(),
                         }
                     }
-                    (KeyCode::Left, _) => {
-                        if active_open == true {
-                            show_comment = false;
+                    (Some(Action::Bottom), ..) => {
+                        match current_tab(active_menu_item, &mut issue_tabs, &mut search_tab) {
+                            Some(tab) if !details_pane.focused => tab.move_selection(tab.rows.len() as isize),
+                            _ => details_pane.bottom(),
                         }
                     }
-                    (KeyCode::Char('1'), _) => {
+                    (_, KeyCode::Char('1'), _) if prompt_open && matches!(active_menu_item, MenuItem::Assignments) => {
                         // close issue
-                        let state;
-                        let list: &Vec<ApiResponseItem>;
-                        if active_open == true {
-                            state = &mut issue_list_state_open;
-                            list = &issues_list_open;
-                        } else {
-                            state = &mut issue_list_state_closed;
-                            list = &issues_list_closed;
-                        }
-                        if let Some(selected) = state.selected() {
-                            let number = list[selected].number;
-                            let repo_owner = list[selected].organization
-                                .as_ref()
-                                .unwrap()
-                                .to_owned();
-                            let repo_name = list[selected].repository.as_ref().unwrap().to_owned();
-                            if prompt_open == true {
-                                update_issue_status(
-                                    repo_owner,
-                                    repo_name,
-                                    number,
-                                    &access_token,
-                                    "closed"
-                                ).await?;
-                                issues_list_open = issues_list_open
-                                    .into_iter()
-                                    .filter(|item| item.number != number)
-                                    .collect::<Vec<ApiResponseItem>>();
-                                issue_list_state_open = ListState::default();
-                                issue_list_state_open.select(Some(0));
-                                issues_list_open_len = issues_list_open_len - 1;
-                                prompt_open = false;
-                            }
-                        }
-                    }
-                    (KeyCode::Char('2'), _) => {
+                        let selected_item = current_tab(MenuItem::Assignments, &mut issue_tabs, &mut search_tab)
+                            .and_then(|tab| tab.selected_item().cloned());
+                        if let Some(item) = selected_item {
+                            match update_issue_status(
+                                item.organization.clone().unwrap_or_default(),
+                                item.repository.clone().unwrap_or_default(),
+                                item.number,
+                                &access_token,
+                                "closed"
+                            ).await {
+                                Ok(()) => {
+                                    for tab in issue_tabs.iter_mut().chain(search_tab.iter_mut()) {
+                                        tab.remove_item(&item.url);
+                                    }
+                                }
+                                Err(e) => {
+                                    status_message = Some(e.to_string());
+                                }
+                            }
+                            prompt_open = false;
+                        }
+                    }
+                    (_, KeyCode::Char('2'), _) if prompt_open => {
                         show_org_modal = true;
                         show_repo_modal = false;
+                        show_label_modal = false;
                         org_or_repo_list.select(Some(0));
-                        match active_menu_item {
-                            MenuItem::Home => {}
-                            MenuItem::Assignments => {
-                                org_list = get_org_list(&issues_list_open);
-                            }
-                            MenuItem::Closed => {
-                                org_list = get_org_list(&issues_list_closed);
-                            }
-                            MenuItem::Refresh => {}
-                            MenuItem::ToReview => {}
+                        if let Some(tab) = current_tab(active_menu_item, &mut issue_tabs, &mut search_tab) {
+                            org_list = get_org_list(&tab.items);
                         }
                     }
-                    (KeyCode::Char('3'), _) => {
+                    (_, KeyCode::Char('3'), _) if prompt_open => {
                         show_repo_modal = true;
                         show_org_modal = false;
+                        show_label_modal = false;
                         org_or_repo_list.select(Some(0));
-                        match active_menu_item {
-                            MenuItem::Home => {}
-                            MenuItem::Assignments => {
-                                repo_list = get_repo_list(&issues_list_open);
+                        if let Some(tab) = current_tab(active_menu_item, &mut issue_tabs, &mut search_tab) {
+                            repo_list = get_repo_list(&tab.items);
+                        }
+                    }
+                    (_, KeyCode::Char('4'), _) if prompt_open => {
+                        show_label_modal = true;
+                        show_org_modal = false;
+                        show_repo_modal = false;
+                        org_or_repo_list.select(Some(0));
+                        if let Some(tab) = current_tab(active_menu_item, &mut issue_tabs, &mut search_tab) {
+                            label_list = get_label_list(&tab.items);
+                        }
+                    }
+                    (Some(Action::Actions), ..)
+                        if active_menu_item.tab_index().is_some() || matches!(active_menu_item, MenuItem::Search) => {
+                            prompt_open = !prompt_open;
+                            show_org_modal = false;
+                            show_repo_modal = false;
+                            show_label_modal = false;
+                        }
+                    (Some(Action::FilterPullRequests), ..) => {
+                        push_filter(&mut filters, Filter::Kind(true));
+                        apply_filters(&filters, &config.staleness, &mut issue_tabs, &mut search_tab);
+                    }
+                    (Some(Action::FilterIssues), ..) => {
+                        push_filter(&mut filters, Filter::Kind(false));
+                        apply_filters(&filters, &config.staleness, &mut issue_tabs, &mut search_tab);
+                    }
+                    (Some(Action::AddFilters), ..) => {
+                        text_input = Some((InputTarget::Filters, TextInput::default()));
+                    }
+                    (Some(Action::RemoveFilter), ..) if !filters.is_empty() => {
+                        filter_list_state.select(Some(0));
+                        filter_popup_open = true;
+                    }
+                    (Some(Action::Sort), ..) => {
+                        if let Some(tab) = current_tab(active_menu_item, &mut issue_tabs, &mut search_tab) {
+                            tab.set_sort(tab.sort.next(), tab.sort_descending);
+                        }
+                    }
+                    (Some(Action::SortOrder), ..) => {
+                        if let Some(tab) = current_tab(active_menu_item, &mut issue_tabs, &mut search_tab) {
+                            tab.set_sort(tab.sort, !tab.sort_descending);
+                        }
+                    }
+                    (Some(Action::Priority), ..) => {
+                        let selected_url = current_tab(active_menu_item, &mut issue_tabs, &mut search_tab)
+                            .and_then(|tab| tab.selected_item().map(|item| item.url.clone()));
+                        if let Some(url) = selected_url {
+                            priorities.cycle(&url);
+                            if let Err(e) = priorities.save() {
+                                status_message = Some(format!("The priority is only kept until the app is closed: {}", e));
                             }
-                            MenuItem::Closed => {
-                                repo_list = get_repo_list(&issues_list_closed);
+                            apply_priorities(&priorities, &mut issue_tabs, &mut search_tab);
+                        }
+                    }
+                    (Some(Action::Group), ..) => {
+                        if let Some(tab) = current_tab(active_menu_item, &mut issue_tabs, &mut search_tab) {
+                            tab.set_grouping(tab.grouping.next());
+                        }
+                    }
+                    (Some(Action::ToggleGroup), ..) => {
+                        if let Some(tab) = current_tab(active_menu_item, &mut issue_tabs, &mut search_tab) {
+                            tab.toggle_group();
+                        }
+                    }
+                    (Some(Action::ClearFilters), ..) => {
+                        filters.clear();
+                        apply_filters(&filters, &config.staleness, &mut issue_tabs, &mut search_tab);
+                    }
+                    (Some(Action::Diff), ..) if !matches!(active_menu_item, MenuItem::Home) => {
+                        let selected_item = current_tab(active_menu_item, &mut issue_tabs, &mut search_tab)
+                            .and_then(|tab| tab.selected_item().cloned());
+                        if let Some(item) = selected_item {
+                            if !item.is_pr {
+                                status_message = Some("The selected item is not a pull request".to_string());
+                            } else {
+                                let repo_owner = item.organization.clone().unwrap_or_default();
+                                let repo_name = item.repository.clone().unwrap_or_default();
+                                match
+                                    futures::try_join!(
+                                        fetch_pull_request(&repo_owner, &repo_name, item.number, &access_token),
+                                        fetch_pr_files(&repo_owner, &repo_name, item.number, &access_token),
+                                        fetch_review_comments(&repo_owner, &repo_name, item.number, &access_token)
+                                    )
+                                {
+                                    Ok((pull_request, files, review_comments)) => {
+                                        diff_view = Some(
+                                            DiffView::new(item, pull_request, files, review_comments)
+                                        );
+                                    }
+                                    Err(e) => {
+                                        status_message = Some(e.to_string());
+                                    }
+                                }
                             }
-                            MenuItem::Refresh => {}
-                            MenuItem::ToReview => {}
                         }
                     }
-                    (KeyCode::Char('n'), _) => {
-                        if active_open == true {
-                            prompt_open = !prompt_open;
+                    (Some(Action::Refresh), ..) => {
+                        diff_view = None;
+                        match init_gh_data(&username, &access_token, &config.tabs).await {
+                            Ok(mut tabs) => {
+                                // Keep the order and grouping chosen in each tab
+                                for (tab, previous) in tabs.iter_mut().zip(issue_tabs.iter()) {
+                                    tab.restore_view(previous);
+                                }
+                                issue_tabs = tabs;
+                                apply_filters(&filters, &config.staleness, &mut issue_tabs, &mut search_tab);
+                            }
+                            Err(e) => {
+                                status_message = Some(e.to_string());
+                            }
                         }
+                        poll_notifications(&mut inbox, &access_token).await;
+                    }
+                    (Some(Action::ToReview), ..) => {
+                        diff_view = None;
+                        active_menu_item = MenuItem::ToReview;
+                    }
+                    (Some(Action::Authored), ..) => {
+                        diff_view = None;
+                        active_menu_item = MenuItem::Authored;
+                    }
+                    (Some(Action::Mentions), ..) => {
+                        diff_view = None;
+                        active_menu_item = MenuItem::Mentions;
                     }
-                    (KeyCode::Char('P'), KeyModifiers::SHIFT) => {
-                        issues_list_open = filter_issues_by_state(&issues_list_open, true);
+                    (Some(Action::Inbox), ..) => {
+                        diff_view = None;
+                        active_menu_item = MenuItem::Inbox;
                     }
-                    (KeyCode::Char('I'), KeyModifiers::SHIFT) => {
-                        issues_list_open = filter_issues_by_state(&issues_list_open, false);
+                    (Some(Action::NextTab), ..) => {
+                        diff_view = None;
+                        active_menu_item = cycle_menu_item(active_menu_item, &config, search_tab.is_some(), 1);
                     }
-                    (KeyCode::Char('r'), KeyModifiers::CONTROL) => {
-                        (
-                            issues_list_open,
-                            issues_list_closed,
-                            assigned_pr_list,
-                            issues_list_open_len,
-                            issues_list_closed_len,
-                            assigned_pr_list_len,
-                        ) = init_gh_data(&username, &access_token).await.unwrap();
+                    (Some(Action::PreviousTab), ..) => {
+                        diff_view = None;
+                        active_menu_item = cycle_menu_item(active_menu_item, &config, search_tab.is_some(), -1);
                     }
-                    (KeyCode::Char('t'), KeyModifiers::CONTROL) => {
-                        if to_review_open == false {
-                            to_review_open = true;
-                            active_menu_item = MenuItem::ToReview;
+                    (Some(Action::FuzzyFilter), ..) => {
+                        if let Some(tab) = current_tab(active_menu_item, &mut issue_tabs, &mut search_tab) {
+                            fuzzy_input = Some(TextInput::new(&tab.fuzzy));
                         }
                     }
+                    (Some(Action::Search), ..) => {
+                        history_index = None;
+                        text_input = Some((InputTarget::SearchQuery, TextInput::default()));
+                    }
+                    (Some(Action::SaveSearch), ..) if matches!(active_menu_item, MenuItem::Search) => {
+                        text_input = Some((InputTarget::SaveSearch(search_query.clone()), TextInput::default()));
+                    }
                     _ => {}
                 }
-            Event::Tick => {}
+            Event::Tick => {
+                if inbox.poll_due() {
+                    poll_notifications(&mut inbox, &access_token).await;
+                }
+            }
         }
     }
     Ok(())
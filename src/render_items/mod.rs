@@ -3,9 +3,27 @@ pub mod render_issues;
 pub mod render_waiting_screen;
 pub mod render_popup;
 pub mod render_error;
+pub mod render_diff;
+pub mod render_input;
+pub mod render_merge;
+pub mod render_checks;
+pub mod render_inbox;
+pub mod render_markdown;
+pub mod render_timeline;
+pub mod render_reactions;
+pub mod render_help;
 
 pub use render_home::render_home;
 pub use render_issues::render_issues;
 pub use render_waiting_screen::render_waiting_screen;
 pub use render_popup::render_popup;
 pub use render_error::render_error;
+pub use render_diff::render_diff;
+pub use render_input::render_input;
+pub use render_merge::render_merge;
+pub use render_checks::render_checks;
+pub use render_inbox::render_inbox;
+pub use render_markdown::render_markdown;
+pub use render_timeline::render_timeline;
+pub use render_reactions::render_reactions;
+pub use render_help::render_help;
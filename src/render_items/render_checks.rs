@@ -0,0 +1,63 @@
+use tui::{
+    Frame,
+    backend::Backend,
+    widgets::{ Block, Borders, Clear, List, ListItem, ListState },
+    style::{ Modifier, Style },
+    text::{ Span, Spans },
+};
+
+use crate::structs::{ Action, ApiResponseItem, CiState, KeyContext, Keymap, Theme };
+use crate::utils::{ centered_rect, format_duration };
+
+pub fn render_checks(rect: &mut Frame<impl Backend>, item: &ApiResponseItem, state: &mut ListState, keymap: &Keymap, theme: &Theme) {
+    let (title, items): (String, Vec<ListItem>) = match &item.ci_status {
+        None => (
+            format!("Checks for #{}", item.number),
+            vec![ListItem::new("No status available for this pull request")],
+        ),
+        Some(ci) => {
+            let passed = ci.checks.iter().filter(|c| c.state == CiState::Success).count();
+            let title = format!(
+                "Checks for #{} on {} - {}/{} passed - {}",
+                item.number,
+                ci.sha.chars().take(7).collect::<String>(),
+                passed,
+                ci.checks.len(),
+                keymap.hint(&[KeyContext::Global], &[(Action::Confirm, "open log"), (Action::Cancel, "close")])
+            );
+            let items = if ci.checks.is_empty() {
+                vec![ListItem::new("No checks reported for the head commit")]
+            } else {
+                ci.checks
+                    .iter()
+                    .map(|check| {
+                        let color = match check.state {
+                            CiState::Success => theme.success,
+                            CiState::Failure => theme.failure,
+                            CiState::Pending => theme.warning,
+                            CiState::Neutral => theme.secondary,
+                        };
+                        ListItem::new(Spans::from(vec![
+                            Span::raw(format!("{} ", check.state.icon())),
+                            Span::styled(format!("{: <35} ", check.name), Style::default().fg(theme.popup_fg)),
+                            Span::styled(format!("{: <20} ", check.conclusion), Style::default().fg(color)),
+                            Span::raw(check.duration.map(format_duration).unwrap_or_default()),
+                        ]))
+                    })
+                    .collect()
+            };
+            (title, items)
+        }
+    };
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .style(theme.popup())
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD).fg(theme.selected))
+        .highlight_symbol(">> ");
+
+    let popup_chunk = centered_rect(100, 20, rect.size());
+
+    rect.render_widget(Clear, popup_chunk);
+    rect.render_stateful_widget(list, popup_chunk, state);
+}
@@ -0,0 +1,212 @@
+use tui::{
+  Frame,
+  backend::Backend,
+  layout::{Constraint, Direction, Layout, Rect},
+  style::{Color, Modifier, Style},
+  text::{Span, Spans},
+  widgets::{Block, BorderType, Borders, List, ListItem, Paragraph},
+};
+use crate::structs::{Action, DiffLine, DiffLineKind, DiffView, KeyContext, Keymap, ReviewComment, Theme};
+
+fn status_indicator(status: &str, theme: &Theme) -> (&'static str, Color) {
+    match status {
+        "added" => ("A", theme.success),
+        "removed" => ("D", theme.failure),
+        "renamed" => ("R", theme.warning),
+        _ => ("M", theme.accent),
+    }
+}
+
+// Whether a comment made on `line`/`side` of the current diff belongs under this diff line
+fn is_anchored_to(line: &DiffLine, comment_line: i32, side: &str) -> bool {
+    match side {
+        "LEFT" => line.kind != DiffLineKind::Added && line.old_line == Some(comment_line),
+        _ => line.kind != DiffLineKind::Removed && line.new_line == Some(comment_line),
+    }
+}
+
+fn thread_spans<'a>(thread: &[&ReviewComment], theme: &Theme) -> Vec<Spans<'a>> {
+    let resolved = thread.first().map(|c| c.resolved).unwrap_or(false);
+    let style = if resolved {
+        Style::default().fg(theme.muted)
+    } else {
+        Style::default().fg(theme.warning)
+    };
+    let mut spans = vec![Spans::from(Span::styled(
+        format!("            ┌ 💬 {} comment(s){}", thread.len(), if resolved { " - resolved" } else { "" }),
+        style.add_modifier(Modifier::BOLD),
+    ))];
+    for comment in thread {
+        for (index, body_line) in comment.body.lines().enumerate() {
+            let author = if index == 0 { format!("{}: ", comment.user.login) } else { "".to_string() };
+            spans.push(Spans::from(Span::styled(format!("            │ {}{}", author, body_line), style)));
+        }
+    }
+    spans
+}
+
+pub fn render_diff(rect: &mut Frame<impl Backend>, area: Rect, diff_view: &mut DiffView, keymap: &Keymap, theme: &Theme) {
+    let data_chunck = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)].as_ref())
+        .split(area);
+
+    let total_additions: i32 = diff_view.files.iter().map(|f| f.additions).sum();
+    let total_deletions: i32 = diff_view.files.iter().map(|f| f.deletions).sum();
+
+    let items: Vec<ListItem> = diff_view.files
+        .iter()
+        .map(|f| {
+            let (indicator, color) = status_indicator(&f.status, theme);
+            let comment_count = diff_view.review_comments.iter().filter(|c| c.path == f.filename).count();
+            let comments = if comment_count > 0 { format!(" 💬{}", comment_count) } else { "".to_string() };
+            ListItem::new(Spans::from(vec![
+                Span::styled(format!("{} ", indicator), Style::default().fg(color)),
+                Span::styled(format!("+{: <4}", f.additions), Style::default().fg(theme.success)),
+                Span::styled(format!("-{: <4}", f.deletions), Style::default().fg(theme.failure)),
+                Span::raw(f.filename.clone()),
+                Span::styled(comments, Style::default().fg(theme.warning)),
+            ]))
+        })
+        .collect();
+
+    let list_border = if diff_view.focus_patch { theme.border } else { theme.accent };
+    let file_list = List::new(items)
+        .block(
+            Block::default()
+                .title(format!("Files ({}) +{} -{}", diff_view.files.len(), total_additions, total_deletions))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(list_border))
+                .border_type(BorderType::Rounded),
+        )
+        .style(Style::default().fg(theme.text))
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+        .highlight_symbol("> ");
+
+    let diff_lines = diff_view.selected_lines();
+    let mut lines: Vec<Spans> = vec![];
+    let mut cursor_row = 0;
+
+    match diff_view.selected_file() {
+        None => lines.push(Spans::from("No files changed")),
+        Some(file) if file.patch.is_none() => lines.push(Spans::from(Span::styled(
+            format!(
+                "Binary file or diff too large to display, press {} to open it in the browser",
+                keymap.keys_for(&[KeyContext::Diff, KeyContext::Global], Action::Confirm)
+            ),
+            Style::default().fg(theme.muted),
+        ))),
+        Some(file) if diff_view.is_collapsed(file) => lines.push(Spans::from(Span::styled(
+            format!(
+                "Large diff collapsed ({} changes), press {} to expand",
+                file.changes,
+                keymap.keys_for(&[KeyContext::Diff, KeyContext::Global], Action::ExpandFile)
+            ),
+            Style::default().fg(theme.warning),
+        ))),
+        Some(file) => {
+            let mut file_comments: Vec<&ReviewComment> = diff_view.review_comments
+                .iter()
+                .filter(|c| c.path == file.filename)
+                .collect();
+            file_comments.sort_by_key(|c| c.id);
+            let outdated = file_comments.iter().filter(|c| c.line.is_none() && c.in_reply_to_id.is_none()).count();
+            if outdated > 0 {
+                lines.push(Spans::from(Span::styled(
+                    format!("{} outdated comment thread(s) not shown", outdated),
+                    Style::default().fg(theme.muted),
+                )));
+            }
+
+            for (index, line) in diff_lines.iter().enumerate() {
+                let mut style = match line.kind {
+                    DiffLineKind::Hunk => Style::default().fg(theme.accent),
+                    DiffLineKind::Added => Style::default().fg(theme.success),
+                    DiffLineKind::Removed => Style::default().fg(theme.failure),
+                    DiffLineKind::Context => Style::default().fg(theme.text),
+                    DiffLineKind::Meta => Style::default().fg(theme.muted),
+                };
+                if diff_view.focus_patch && index == diff_view.cursor {
+                    style = style.bg(theme.popup_bg).add_modifier(Modifier::BOLD);
+                    cursor_row = lines.len();
+                }
+                let sign = match line.kind {
+                    DiffLineKind::Added => "+",
+                    DiffLineKind::Removed => "-",
+                    _ => " ",
+                };
+                let gutter = format!(
+                    "{: >5} {: >5} ",
+                    line.old_line.map(|n| n.to_string()).unwrap_or_default(),
+                    line.new_line.map(|n| n.to_string()).unwrap_or_default(),
+                );
+                lines.push(Spans::from(vec![
+                    Span::styled(gutter, Style::default().fg(theme.muted)),
+                    Span::styled(format!("{}{}", sign, line.content), style),
+                ]));
+
+                // Threads anchored to this line, then the comments waiting in the pending review
+                for root in file_comments.iter().filter(|c| c.in_reply_to_id.is_none()) {
+                    let anchored = root.line
+                        .map(|l| is_anchored_to(line, l, root.side.as_deref().unwrap_or("RIGHT")))
+                        .unwrap_or(false);
+                    if anchored {
+                        let thread: Vec<&ReviewComment> = file_comments
+                            .iter()
+                            .filter(|c| c.thread_id() == root.id)
+                            .copied()
+                            .collect();
+                        lines.extend(thread_spans(&thread, theme));
+                    }
+                }
+                for pending in diff_view.pending_comments.iter().filter(|c| c.path == file.filename) {
+                    if is_anchored_to(line, pending.line, &pending.side) {
+                        for body_line in pending.body.lines() {
+                            lines.push(Spans::from(Span::styled(
+                                format!("            📝 pending: {}", body_line),
+                                Style::default().fg(theme.special),
+                            )));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Keep the cursor inside the visible part of the patch
+    let visible_rows = data_chunck[1].height.saturating_sub(2) as usize;
+    if cursor_row < diff_view.scroll as usize {
+        diff_view.scroll = cursor_row as u16;
+    } else if visible_rows > 0 && cursor_row >= diff_view.scroll as usize + visible_rows {
+        diff_view.scroll = (cursor_row + 1 - visible_rows) as u16;
+    }
+
+    let title = match diff_view.selected_file() {
+        Some(file) => format!(
+            "{} (+{} -{}){}",
+            file.filename,
+            file.additions,
+            file.deletions,
+            if diff_view.pending_comments.is_empty() {
+                "".to_string()
+            } else {
+                format!(" - {} pending comment(s)", diff_view.pending_comments.len())
+            }
+        ),
+        None => "Diff".to_string(),
+    };
+
+    let patch_border = if diff_view.focus_patch { theme.accent } else { theme.border };
+    let diff = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(patch_border))
+                .border_type(BorderType::Rounded),
+        )
+        .scroll((diff_view.scroll, 0));
+
+    rect.render_stateful_widget(file_list, data_chunck[0], &mut diff_view.file_list_state);
+    rect.render_widget(diff, data_chunck[1]);
+}
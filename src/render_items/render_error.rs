@@ -1,16 +1,17 @@
-use tui::{Frame, backend::Backend, widgets::{Block, Borders, Clear, Paragraph}, style::{Style, Color}, text::Text};
+use tui::{Frame, backend::Backend, widgets::{Block, Borders, Clear, Paragraph}, style::Style, text::Text};
 
+use crate::structs::Theme;
 use crate::utils::centered_rect;
 
-pub fn render_error(rect: &mut Frame<impl Backend>, message: &str) {
+pub fn render_error(rect: &mut Frame<impl Backend>, message: &str, theme: &Theme) {
   let popup = Block::default()
       .borders(Borders::ALL)
       .title("Message")
-      .style(Style::default().fg(Color::White).bg(Color::DarkGray))
+      .style(theme.popup())
       .borders(Borders::ALL);
 
   let paragraph = Paragraph::new(Text::from(message))
-      .style(Style::default().fg(Color::White));
+      .style(Style::default().fg(theme.popup_fg));
 
   let popup_chunk = centered_rect(35, 10, rect.size());
 
@@ -0,0 +1,38 @@
+use tui::{
+    Frame,
+    backend::Backend,
+    widgets::{ Block, Borders, Clear, Paragraph },
+    style::{ Modifier, Style },
+    text::{ Span, Spans },
+};
+
+use crate::structs::{ Action, HelpView, KeyContext, Keymap, Theme };
+use crate::utils::centered_rect;
+
+// One line per entry, the keys padded to the widest so the descriptions line up
+pub fn help_lines<'a>(entries: &[(String, String)], theme: &Theme) -> Vec<Spans<'a>> {
+    let width = entries.iter().map(|(keys, _)| keys.chars().count()).max().unwrap_or(0);
+    entries
+        .iter()
+        .map(|(keys, description)| {
+            Spans::from(vec![
+                Span::styled(format!("{: <width$}  ", keys, width = width), Style::default().fg(theme.accent).add_modifier(Modifier::BOLD)),
+                Span::raw(description.clone()),
+            ])
+        })
+        .collect()
+}
+
+pub fn render_help(rect: &mut Frame<impl Backend>, view: &HelpView, keymap: &Keymap, theme: &Theme) {
+    let paragraph = Paragraph::new(help_lines(&view.entries, theme))
+        .block(Block::default().borders(Borders::ALL).title(format!("Keys - {} - {}", view.title, keymap.hint(&[KeyContext::Global], &[(Action::Cancel, "close")]))))
+        .style(theme.popup())
+        .scroll((view.scroll, 0));
+
+    let size = rect.size();
+    let height = (view.entries.len() as u16 + 2).min(size.height.saturating_sub(4));
+    let popup_chunk = centered_rect(size.width.saturating_sub(10).min(110), height, size);
+
+    rect.render_widget(Clear, popup_chunk);
+    rect.render_widget(paragraph, popup_chunk);
+}
@@ -1,12 +1,15 @@
 use tui::{
   layout::Alignment,
-  style::{Color, Style, Modifier},
+  style::{Style, Modifier},
   text::{Span, Spans},
   widgets::{Block, BorderType, Borders, Paragraph}
 };
+use crate::structs::Theme;
+use super::render_help::help_lines;
 
-pub fn render_home<'a>(opened: &i32, closed: &i32, review: &i32, username: &String) -> Paragraph<'a> {
-  let home = Paragraph::new(vec![
+#[allow(clippy::too_many_arguments)]
+pub fn render_home<'a>(opened: &i32, closed: &i32, review: &i32, authored: &i32, mentions: &i32, notifications: &i32, username: &String, keys: &[(String, String)], help_keys: &str, theme: &Theme) -> Paragraph<'a> {
+  let mut lines = vec![
       Spans::from(vec![Span::raw(format!("Welcome to your GitHub assistant, {} ⭐️", username))]),
       Spans::from(vec![Span::raw("")]),
       Spans::from(vec![Span::raw(format!(
@@ -21,37 +24,30 @@ pub fn render_home<'a>(opened: &i32, closed: &i32, review: &i32, username: &Stri
           "{} To review",
           review,
       ))]),
+      Spans::from(vec![Span::raw(format!(
+          "{} pull requests authored",
+          authored,
+      ))]),
+      Spans::from(vec![Span::raw(format!(
+          "{} threads mentioning you",
+          mentions,
+      ))]),
+      Spans::from(vec![Span::raw(format!(
+          "{} unread notifications 🔔",
+          notifications,
+      ))]),
       Spans::from(vec![Span::raw("")]),
 
-      Spans::from(vec![Span::styled("Navigate:", Style::default().add_modifier(Modifier::BOLD).fg(Color::LightCyan))]),
-      Spans::from(vec![Span::styled("Up: ", Style::default().add_modifier(Modifier::BOLD).fg(Color::LightCyan)), Span::raw("Move up in the list")]),
-      Spans::from(vec![Span::styled("Down: ", Style::default().add_modifier(Modifier::BOLD).fg(Color::LightCyan)), Span::raw("Move down in the list")]),
-      Spans::from(vec![Span::styled("Right: ", Style::default().add_modifier(Modifier::BOLD).fg(Color::LightCyan)), Span::raw("Display comments for the selected issue/PR")]),
-      Spans::from(vec![Span::styled("Left: ", Style::default().add_modifier(Modifier::BOLD).fg(Color::LightCyan)), Span::raw("Hide comments for the selected issue/PR")]),
-      Spans::from(vec![Span::raw("")]),
-      Spans::from(vec![Span::styled("Commands",Style::default().add_modifier(Modifier::BOLD).fg(Color::LightCyan))]),
+      Spans::from(vec![Span::styled(format!("Keys - press {} on any screen for its own", help_keys), Style::default().add_modifier(Modifier::BOLD).fg(theme.accent))]),
       Spans::from(vec![Span::raw("")]),
-
-      Spans::from(vec![Span::styled("CTRL + a : ", Style::default().add_modifier(Modifier::BOLD).fg(Color::LightCyan)), Span::raw("show assignment")]),
-      Spans::from(vec![Span::styled("CTRL + c: ", Style::default().add_modifier(Modifier::BOLD).fg(Color::LightCyan)), Span::raw("show closed")]),
-      Spans::from(vec![Span::styled("CTRL + h: ", Style::default().add_modifier(Modifier::BOLD).fg(Color::LightCyan)), Span::raw("home")]),
-      Spans::from(vec![Span::styled("q: ", Style::default().add_modifier(Modifier::BOLD).fg(Color::LightCyan)), Span::raw("close app")]),
-      Spans::from(vec![Span::styled("CTRL + r: ", Style::default().add_modifier(Modifier::BOLD).fg(Color::LightCyan)), Span::raw("reload content")]),
-      Spans::from(vec![Span::styled("SHIFT + P: ", Style::default().add_modifier(Modifier::BOLD).fg(Color::LightCyan)), Span::raw("show only pull requests")]),
-      Spans::from(vec![Span::styled("SHIFT + i: ", Style::default().add_modifier(Modifier::BOLD).fg(Color::LightCyan)), Span::raw("show only issues")]),
-      Spans::from(vec![Span::styled("ENTER: ", Style::default().add_modifier(Modifier::BOLD).fg(Color::LightCyan)), Span::raw("open the issue in the browser")]),
-      Spans::from(vec![Span::styled("n: ", Style::default().add_modifier(Modifier::BOLD).fg(Color::LightCyan)), Span::raw("show actions")]),
-      Spans::from(vec![Span::styled("1: ", Style::default().add_modifier(Modifier::BOLD).fg(Color::LightCyan)), Span::raw("close issue")]),
-      Spans::from(vec![Span::styled("2: ", Style::default().add_modifier(Modifier::BOLD).fg(Color::LightCyan)), Span::raw("Show organisations")]),
-      Spans::from(vec![Span::styled("3: ", Style::default().add_modifier(Modifier::BOLD).fg(Color::LightCyan)), Span::raw("Show repositories")]),
-
-
-    ])
+  ];
+  lines.extend(help_lines(keys, theme));
+  let home = Paragraph::new(lines)
     .alignment(Alignment::Center)
     .block(
     Block::default()
         .borders(Borders::ALL)
-        .style(Style::default().fg(Color::White))
+        .style(Style::default().fg(theme.text))
         .title("Home")
         .border_type(BorderType::Plain)
         .border_type(BorderType::Rounded),
@@ -0,0 +1,85 @@
+use tui::{
+  Frame,
+  backend::Backend,
+  layout::Rect,
+  style::{Color, Modifier, Style},
+  text::{Span, Spans},
+  widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph, Wrap},
+};
+use crate::structs::{Action, Inbox, KeyContext, Keymap, Theme};
+
+fn reason_color(reason: &str, theme: &Theme) -> Color {
+    match reason {
+        "mention" | "team_mention" => theme.warning,
+        "review_requested" => theme.special,
+        "assign" => theme.accent,
+        "ci_activity" => theme.failure,
+        _ => theme.secondary,
+    }
+}
+
+pub fn render_inbox(rect: &mut Frame<impl Backend>, area: Rect, inbox: &Inbox, keymap: &Keymap, theme: &Theme) {
+    let block = Block::default()
+        .title(format!(
+            "Inbox ({} unread) - {}",
+            inbox.unread_count(),
+            keymap.hint(
+                &[KeyContext::Inbox, KeyContext::Global],
+                &[(Action::Confirm, "open"), (Action::MarkRead, "mark read"), (Action::MarkDone, "done"), (Action::Unsubscribe, "unsubscribe")]
+            )
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.border))
+        .border_type(BorderType::Rounded);
+
+    if let Some(error) = &inbox.error {
+        let paragraph = Paragraph::new(format!("Notifications unavailable: {}", error))
+            .block(block)
+            .wrap(Wrap { trim: true });
+        rect.render_widget(paragraph, area);
+        return;
+    }
+    if inbox.notifications.is_empty() {
+        rect.render_widget(Paragraph::new("No notifications 🎉").block(block), area);
+        return;
+    }
+
+    // Repository headers are inserted between the groups, so the selection has to be shifted past them
+    let mut items: Vec<ListItem> = vec![];
+    let mut selected_row = None;
+    let mut current_repository = "";
+    for (index, notification) in inbox.notifications.iter().enumerate() {
+        if notification.repository.full_name != current_repository {
+            current_repository = &notification.repository.full_name;
+            items.push(ListItem::new(Spans::from(Span::styled(
+                format!("▾ {}", current_repository),
+                Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+            ))));
+        }
+        if inbox.state.selected() == Some(index) {
+            selected_row = Some(items.len());
+        }
+        let title_style = if notification.unread {
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.muted)
+        };
+        items.push(ListItem::new(Spans::from(vec![
+            Span::styled(if notification.unread { "  ● " } else { "    " }, Style::default().fg(theme.accent)),
+            Span::styled(
+                format!("{: <18}", notification.reason_label()),
+                Style::default().fg(reason_color(&notification.reason, theme)),
+            ),
+            Span::styled(format!("{: <12}", notification.subject.kind), Style::default().fg(theme.secondary)),
+            Span::styled(notification.subject.title.clone(), title_style),
+        ])));
+    }
+
+    let list = List::new(items)
+        .block(block)
+        .style(Style::default().fg(theme.text))
+        .highlight_symbol("> ");
+    let mut state = ListState::default();
+    state.select(selected_row);
+    rect.render_stateful_widget(list, area, &mut state);
+}
@@ -0,0 +1,52 @@
+use tui::{
+    Frame,
+    backend::Backend,
+    widgets::{ Block, Borders, Clear, Paragraph, Wrap },
+    style::{ Modifier, Style },
+    text::{ Span, Spans, Text },
+};
+
+use crate::structs::{ TextInput, Theme };
+use crate::utils::centered_rect;
+
+// Lines of the input value, the cursor is drawn as a reversed cell
+pub fn input_spans<'a>(input: &TextInput, show_cursor: bool) -> Vec<Spans<'a>> {
+    let mut lines: Vec<Spans> = vec![];
+    let mut current: Vec<Span> = vec![];
+    for (index, c) in input.value.chars().chain(std::iter::once(' ')).enumerate() {
+        let style = if show_cursor && index == input.cursor {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        };
+        if c == '\n' {
+            current.push(Span::styled(" ", style));
+            lines.push(Spans::from(std::mem::take(&mut current)));
+        } else {
+            current.push(Span::styled(c.to_string(), style));
+        }
+    }
+    lines.push(Spans::from(current));
+    lines
+}
+
+pub fn render_input(rect: &mut Frame<impl Backend>, title: &str, input: &TextInput, theme: &Theme) {
+    let mut lines = input_spans(input, true);
+    lines.push(Spans::from(""));
+    let hint = if input.multiline {
+        "ENTER: confirm - ALT + ENTER: new line - ESC: cancel"
+    } else {
+        "ENTER: confirm - ESC: cancel"
+    };
+    lines.push(Spans::from(Span::styled(hint, Style::default().fg(theme.accent))));
+
+    let paragraph = Paragraph::new(Text::from(lines))
+        .block(Block::default().borders(Borders::ALL).title(title.to_string()))
+        .style(theme.popup())
+        .wrap(Wrap { trim: false });
+
+    let popup_chunk = centered_rect(60, 12, rect.size());
+
+    rect.render_widget(Clear, popup_chunk);
+    rect.render_widget(paragraph, popup_chunk);
+}
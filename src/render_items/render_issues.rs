@@ -1,53 +1,263 @@
 use tui::{
-  layout::{Constraint},
-  style::{Color, Modifier, Style},
+  layout::Rect,
+  style::{Modifier, Style},
   text::{Span, Spans},
-  widgets::{Block, BorderType, Borders, Cell, List, ListItem, Row, Table},
+  widgets::{Block, BorderType, Borders, List, ListItem, Paragraph},
 };
-use crate::structs::ApiResponseItem;
-use chrono::{Duration as ChronoDuration, Utc, DateTime};
+use crate::structs::{Action, ApiResponseItem, CommentFilter, DetailsPane, Grouping, IssueComments, IssueTab, KeyContext, Keymap, Label, PullRequest, TabRow, Theme};
+use crate::utils::{format_age, mentions_user};
+use super::render_markdown;
 use textwrap::wrap;
-use crossterm::terminal::size;
 
 
-pub fn render_issues<'a>(issues: &Vec<ApiResponseItem>, selected_issue_index: Option<usize>, show_comment: bool) -> (List<'a>, Table<'a>) {
-    let mut count = 0;
-    // Determine the terminal width, with a default value if it cannot be determined
-    let terminal_size = size().unwrap_or_default();
-    let terminal_width = terminal_size.0 as usize;
-    let percentage = 0.65;
-    let body_width = (terminal_width as f32 * percentage) as usize;
+fn review_state_span<'a>(login: &str, state: &str, theme: &Theme) -> Span<'a> {
+    let (label, color) = match state {
+        "APPROVED" => ("✅ approved", theme.success),
+        "CHANGES_REQUESTED" => ("❌ changes requested", theme.failure),
+        "COMMENTED" => ("💬 commented", theme.text),
+        "DISMISSED" => ("dismissed", theme.muted),
+        _ => ("⏳ requested", theme.warning),
+    };
+    Span::styled(format!("{} {}  ", login, label), Style::default().fg(color))
+}
 
-    let items: Vec<ListItem> = issues
-        .iter()
-        .map(|i| {
-            count += 1;
-            let updated_at = i.updated_at.parse::<DateTime<Utc>>().unwrap();
-            let now = Utc::now();
-            let diff = now.signed_duration_since(updated_at);
-
-            let color = if diff > ChronoDuration::days(90) {
-                Color::Red
-            } else if diff > ChronoDuration::days(60) {
-                Color::Yellow
+// Short review decision shown in front of the pull requests of the list
+fn review_decision_span<'a>(pull_request: Option<&PullRequest>, theme: &Theme) -> Span<'a> {
+    match pull_request.map(|pr| pr.review_decision()) {
+        Some("Approved") => Span::styled("✔ ", Style::default().fg(theme.success)),
+        Some("Changes requested") => Span::styled("✘ ", Style::default().fg(theme.failure)),
+        Some(_) => Span::styled("• ", Style::default().fg(theme.warning)),
+        None => Span::raw(""),
+    }
+}
+
+fn pull_request_spans<'a>(pull_request: &PullRequest, theme: &Theme) -> Vec<Spans<'a>> {
+    let (mergeability, _) = pull_request.mergeability();
+    let decision_color = match pull_request.review_decision() {
+        "Approved" => theme.success,
+        "Changes requested" => theme.failure,
+        _ => theme.warning,
+    };
+    let mut reviewers: Vec<Span> = vec![Span::raw("Reviewers: ")];
+    let states = pull_request.reviewer_states();
+    if states.is_empty() {
+        reviewers.push(Span::raw("none"));
+    }
+    reviewers.extend(states.iter().map(|(login, state)| review_state_span(login, state, theme)));
+
+    vec![
+        Spans::from(vec![
+            Span::raw(format!("{} wants to merge {} into {}", pull_request.user.login, pull_request.head.label, pull_request.base.label)),
+            if pull_request.draft {
+                Span::styled("  [draft]", Style::default().fg(theme.muted).add_modifier(Modifier::BOLD))
             } else {
-                Color::White
+                Span::raw("")
+            },
+        ]),
+        Spans::from(vec![
+            Span::raw(format!("{} commit(s), {} file(s) changed ", pull_request.commits, pull_request.changed_files)),
+            Span::styled(format!("+{} ", pull_request.additions), Style::default().fg(theme.success)),
+            Span::styled(format!("-{}", pull_request.deletions), Style::default().fg(theme.failure)),
+        ]),
+        Spans::from(vec![
+            Span::raw("Review decision: "),
+            Span::styled(pull_request.review_decision(), Style::default().fg(decision_color)),
+        ]),
+        Spans::from(reviewers),
+        Spans::from(format!("Mergeable: {}", mergeability)),
+    ]
+}
+
+// Labels as chips painted with their GitHub color
+fn label_spans<'a>(labels: &[Label]) -> Vec<Span<'a>> {
+    let mut spans = vec![];
+    for label in labels {
+        spans.push(Span::styled(
+            format!(" {} ", label.name),
+            Style::default().fg(label.foreground()).bg(label.background()),
+        ));
+        spans.push(Span::raw(" "));
+    }
+    spans
+}
+
+// Chips of the labels followed by the description of each label that has one
+fn label_lines<'a>(labels: &[Label], theme: &Theme) -> Vec<Spans<'a>> {
+    if labels.is_empty() {
+        return vec![Spans::from("N/A")];
+    }
+    let mut lines = vec![Spans::from(label_spans(labels))];
+    lines.extend(labels.iter().filter_map(|label| {
+        label.description.as_ref().filter(|description| !description.is_empty()).map(|description| {
+            Spans::from(vec![
+                Span::styled(format!("{}: ", label.name), Style::default().fg(label.background())),
+                Span::styled(description.clone(), Style::default().fg(theme.muted)),
+            ])
+        })
+    }));
+    lines
+}
+
+// Badge of the role of a comment author in the repository, none for outsiders
+fn role_badge<'a>(association: &str, theme: &Theme) -> Option<Span<'a>> {
+    let color = match association {
+        "OWNER" => theme.special,
+        "MEMBER" => theme.link,
+        "COLLABORATOR" => theme.success,
+        "CONTRIBUTOR" | "FIRST_TIME_CONTRIBUTOR" | "FIRST_TIMER" => theme.secondary,
+        _ => return None,
+    };
+    Some(Span::styled(format!(" {} ", association.replace('_', " ")), Style::default().fg(theme.on_accent).bg(color)))
+}
+
+// Comment framed as a card: author, role, age and reactions around its markdown body
+fn comment_card<'a>(comment: &IssueComments, mentioned: bool, width: usize, theme: &Theme) -> Vec<Spans<'a>> {
+    let border = Style::default().fg(if mentioned { theme.warning } else { theme.muted });
+    let author = if mentioned {
+        Style::default().fg(theme.warning).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.text).add_modifier(Modifier::BOLD)
+    };
+    let mut header = vec![
+        Span::styled("╭─ ", border),
+        Span::styled(format!("{}{} ", if mentioned { "📣 " } else { "" }, comment.user.login), author),
+    ];
+    if let Some(badge) = role_badge(&comment.author_association, theme) {
+        header.push(badge);
+        header.push(Span::raw(" "));
+    }
+    let age = format_age(&comment.created_at);
+    if !age.is_empty() {
+        header.push(Span::styled(
+            format!("{} ago{}", age, if comment.edited() { " (edited)" } else { "" }),
+            Style::default().fg(theme.muted),
+        ));
+    }
+
+    let mut lines = vec![Spans::from(header)];
+    for line in render_markdown(&comment.body, width.saturating_sub(2), theme) {
+        let mut spans = vec![Span::styled("│ ", border)];
+        spans.extend(line.0);
+        lines.push(Spans::from(spans));
+    }
+    let reactions = comment.reactions.as_ref().map(|reactions| reactions.summary()).unwrap_or_default();
+    lines.push(Spans::from(vec![Span::styled("╰─ ", border), Span::raw(reactions)]));
+    lines
+}
+
+// Section of the details pane: a colored header, its content and a blank line
+fn section<'a>(lines: &mut Vec<Spans<'a>>, title: &str, content: Vec<Spans<'a>>, theme: &Theme) {
+    lines.push(Spans::from(Span::styled(title.to_string(), Style::default().fg(theme.accent))));
+    lines.extend(content);
+    lines.push(Spans::from(""));
+}
+
+// Border of the pane that has the focus
+fn focus_style(focused: bool, theme: &Theme) -> Style {
+    if focused {
+        Style::default().fg(theme.accent)
+    } else {
+        Style::default().fg(theme.border)
+    }
+}
+
+// Title with the chars matched by the fuzzy filter highlighted
+fn title_spans<'a>(title: &str, matched: &[usize], style: Style, theme: &Theme) -> Vec<Span<'a>> {
+    if matched.is_empty() {
+        return vec![Span::styled(format!("{: <20}", title), style)];
+    }
+    let highlight = style.fg(theme.selected).add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+    title
+        .chars()
+        .enumerate()
+        .map(|(index, c)| Span::styled(c.to_string(), if matched.contains(&index) { highlight } else { style }))
+        .collect()
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn render_issues<'a>(
+    tab: &IssueTab,
+    show_comment: bool,
+    filter_editing: bool,
+    username: &str,
+    pane: &mut DetailsPane,
+    details_area: Rect,
+    comment_filter: &CommentFilter,
+    keymap: &Keymap,
+    theme: &Theme,
+) -> (List<'a>, Paragraph<'a>) {
+    let count = tab.visible.len();
+    // Width inside the borders of the details pane
+    let body_width = details_area.width.saturating_sub(2) as usize;
+
+    let items: Vec<ListItem> = tab.rows
+        .iter()
+        .map(|row| {
+            let (index, matched) = match row {
+                TabRow::Header { name, count, collapsed } => {
+                    return ListItem::new(Spans::from(Span::styled(
+                        format!("{} {} ({})", if *collapsed { "▸" } else { "▾" }, name, count),
+                        Style::default().fg(theme.accent).add_modifier(Modifier::BOLD),
+                    )));
+                }
+                TabRow::Item(position) => &tab.visible[*position],
             };
-            let indicator;
-            if i.is_pr {
-                indicator = "🔗";
-            } else {
-                indicator = "📄";
+            let i = &tab.items[*index];
+            let color = tab.staleness.color(i, theme);
+            let indicator = if i.is_pr { "🔗" } else { "📄" };
+            let ci_indicator = i.ci_status.as_ref().map(|ci| ci.state.icon()).unwrap_or("  ");
+            let priority = tab.priorities.get(&i.url).map(|p| format!("P{} ", p)).unwrap_or_default();
+            let draft = if i.pr_details.as_ref().map(|pr| pr.draft).unwrap_or(false) { "[draft] " } else { "" };
+            let mut spans = vec![
+                Span::styled(
+                    format!("{: <4} | {: >3} | {: <1}{} |", i.number, format_age(&i.updated_at), indicator, ci_indicator),
+                    Style::default().fg(color),
+                ),
+                review_decision_span(i.pr_details.as_ref(), theme),
+                Span::styled(priority, Style::default().fg(theme.warning).add_modifier(Modifier::BOLD)),
+                Span::styled(draft, Style::default().fg(color)),
+            ];
+            spans.extend(title_spans(&i.title, matched, Style::default().fg(color), theme));
+            if !i.labels.is_empty() {
+                spans.push(Span::raw(" "));
+                spans.extend(label_spans(&i.labels));
             }
-            ListItem::new(Spans::from(vec![
-                Span::styled(format!("{: <4} | {: <1} |{: <20}", i.number, indicator, i.title), Style::default().fg(color)),
-            ]))
+            ListItem::new(Spans::from(spans))
         })
         .collect();
 
+    let mut list_title = vec![Span::raw(if tab.filters.is_empty() && tab.fuzzy.is_empty() && !filter_editing {
+        format!("{} ({} total) ", tab.title, count)
+    } else {
+        format!("{} ({}/{}) ", tab.title, count, tab.items.len())
+    })];
+    list_title.push(Span::styled(
+        format!("{} {} ", if tab.sort_descending { "↓" } else { "↑" }, tab.sort.label()),
+        Style::default().fg(theme.accent),
+    ));
+    if tab.grouping != Grouping::None {
+        list_title.push(Span::styled(format!("by {} ", tab.grouping.label()), Style::default().fg(theme.accent)));
+    }
+    // One chip per filter of the stack, the remove filter key lists them for removal
+    for filter in &tab.filters {
+        list_title.push(Span::styled(
+            format!(" {} ", filter.label()),
+            Style::default().fg(theme.on_accent).bg(theme.accent),
+        ));
+        list_title.push(Span::raw(" "));
+    }
+    if !tab.fuzzy.is_empty() || filter_editing {
+        list_title.push(Span::raw(format!("filter: {}{}", tab.fuzzy, if filter_editing { "▏" } else { "" })));
+    }
     let issue_list = List::new(items)
-        .block(Block::default().title(format!("Assignments ({} total)", count)).borders(Borders::ALL).border_type(BorderType::Rounded))
-        .style(Style::default().fg(Color::White))
+        .block(
+            Block::default()
+                .title(Spans::from(list_title))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(focus_style(!pane.focused, theme)),
+        )
+        .style(Style::default().fg(theme.text))
         .highlight_symbol("> ");
 
     let binding = ApiResponseItem {
@@ -55,6 +265,9 @@ pub fn render_issues<'a>(issues: &Vec<ApiResponseItem>, selected_issue_index: Op
         title: "".to_owned(),
         number: 0,
         body: None,
+        user: None,
+        comments: 0,
+        reactions: None,
         state: "".to_owned(),
         repository: None,
         organization: None,
@@ -64,166 +277,101 @@ pub fn render_issues<'a>(issues: &Vec<ApiResponseItem>, selected_issue_index: Op
         comments_list: vec![],
         comments_url: "".to_owned(),
         is_pr: false,
+        ci_status: None,
+        pr_details: None,
     };
 
-    let selected_issue = selected_issue_index
-        .map(|i| &issues[i])
-        .unwrap_or(&binding);
+    let selected_issue = tab.selected_item().unwrap_or(&binding);
 
-    let body_height = match &selected_issue.body {
-          Some(body) => body.lines().count() + 1,
-          None => 1,
-    };
-    let issue_details;
-
-    if show_comment == true {
-        let comments_text: Vec<String> = selected_issue
+    let mut lines: Vec<Spans> = vec![];
+    section(&mut lines, "Number", vec![Spans::from(selected_issue.number.to_string())], theme);
+    if show_comment {
+        let mut comments_lines: Vec<Spans> = vec![];
+        let organization = selected_issue.organization.as_deref().unwrap_or_default();
+        let repository = selected_issue.repository.as_deref().unwrap_or_default();
+        let hidden = selected_issue.comments_list
+            .iter()
+            .filter(|comment| comment_filter.hides(organization, repository, &comment.user.login))
+            .count();
+        for comment in selected_issue
             .comments_list
             .iter()
-            .filter(|comment| comment.user.login != "netlify[bot]" && comment.user.login != "gatsby-cloud[bot]" )
-            .map(|comment| {
-              let formatted_body = wrap(&comment.body, body_width).join("\n");
-              format!("{}: {}", comment.user.login, formatted_body)
-            })
-            .collect();
-        let comments_cell;
-        if comments_text.len() == 0 {
-            comments_cell = Cell::from("No comments");
-        } else {
-            comments_cell = Cell::from(comments_text.join("\n\n"));
+            .filter(|comment| pane.show_hidden || !comment_filter.hides(organization, repository, &comment.user.login))
+        {
+            // Comments pinging the user stand out from the rest of the thread
+            let mentioned = mentions_user(&comment.body, username);
+            if !comments_lines.is_empty() {
+                comments_lines.push(Spans::from(""));
+            }
+            comments_lines.extend(comment_card(comment, mentioned, body_width, theme));
         }
-        issue_details = Table::new(vec![
-            Row::new(vec![Cell::from("Number")])
-                .style(Style::default().fg(Color::LightCyan))
-                .height(1),
-            Row::new(vec![
-                Cell::from(selected_issue.number.to_string()),
-                Cell::from(selected_issue.title.clone()),
-                Cell::from(selected_issue.labels.iter().map(|l| l.name.as_str()).collect::<Vec<_>>().join(", ")),
-                Cell::from(selected_issue.state.clone()),
-            ])
-            .style(Style::default().fg(Color::White))
-            .height(2),
-            Row::new(vec![
-                Cell::from("Comments")
-                    .style(Style::default().fg(Color::LightCyan))
-            ]),
-            Row::new(vec![comments_cell])
-                .style(Style::default().fg(Color::White))
-                .height(25),
-        ])
+        if comments_lines.is_empty() {
+            comments_lines.push(Spans::from("No comments"));
+        }
+        let toggle_keys = keymap.keys_for(&[KeyContext::List, KeyContext::Global], Action::ToggleHidden);
+        let title = match (hidden, pane.show_hidden) {
+            (0, _) => "Comments".to_string(),
+            (hidden, false) => format!("Comments - {} hidden, {} to show", hidden, toggle_keys),
+            (hidden, true) => format!("Comments - showing {} hidden, {} to hide", hidden, toggle_keys),
+        };
+        section(&mut lines, &title, comments_lines, theme);
+    } else {
+        section(&mut lines, "Repository", vec![Spans::from(selected_issue.repository.clone().unwrap_or_else(|| "N/A".to_string()))], theme);
+        section(&mut lines, "Organization", vec![Spans::from(selected_issue.organization.clone().unwrap_or_else(|| "N/A".to_string()))], theme);
+        section(&mut lines, "Title", wrap(&selected_issue.title, body_width.max(10)).iter().map(|line| Spans::from(line.to_string())).collect(), theme);
+        section(&mut lines, "Labels", label_lines(&selected_issue.labels, theme), theme);
+        let reactions = selected_issue.reactions.as_ref().map(|reactions| reactions.summary()).unwrap_or_default();
+        if !reactions.is_empty() {
+            section(&mut lines, "Reactions", vec![Spans::from(reactions)], theme);
+        }
+        if let Some(pull_request) = &selected_issue.pr_details {
+            section(&mut lines, "Pull request", pull_request_spans(pull_request, theme), theme);
+        }
+        let body_lines = match &selected_issue.body {
+            Some(body) if !body.trim().is_empty() => render_markdown(body, body_width, theme),
+            _ => vec![Spans::from("N/A")],
+        };
+        section(&mut lines, "Description", body_lines, theme);
+        section(&mut lines, "Created at", vec![Spans::from(selected_issue.created_at.clone())], theme);
+        let mut updated = vec![Spans::from(Span::styled(
+            format!("{} ({} ago)", selected_issue.updated_at, format_age(&selected_issue.updated_at)),
+            Style::default().fg(tab.staleness.color(selected_issue, theme)),
+        ))];
+        if let Some(rule) = tab.staleness.sla_rule(selected_issue) {
+            updated.push(Spans::from(Span::styled(
+                format!("Open for more than {} days, past its SLA", rule.older_than_days),
+                Style::default().fg(tab.staleness.color(selected_issue, theme)).add_modifier(Modifier::BOLD),
+            )));
+        }
+        section(&mut lines, "Updated at", updated, theme);
+    }
+
+    pane.update(&selected_issue.url, lines.len(), details_area.height.saturating_sub(2));
+    // Position in the content, with arrows when there is more above or below
+    let mut details_title = vec![Span::raw("Details ")];
+    if pane.line_count > pane.height {
+        let last = (pane.scroll + pane.height).min(pane.line_count);
+        details_title.push(Span::styled(
+            format!(
+                "{}{}-{}/{}{} ",
+                if pane.scroll > 0 { "▲ " } else { "" },
+                pane.scroll + 1,
+                last,
+                pane.line_count,
+                if last < pane.line_count { " ▼" } else { "" },
+            ),
+            Style::default().fg(theme.accent),
+        ));
+    }
+    let issue_details = Paragraph::new(lines)
         .block(
             Block::default()
-                .title("Details")
-                .borders(Borders::ALL),
-        )
-        .widths(&[Constraint::Min(0)])
-        .highlight_style(
-            Style::default()
-                .add_modifier(Modifier::BOLD)
-                .fg(Color::LightMagenta),
+                .title(Spans::from(details_title))
+                .border_type(BorderType::Rounded)
+                .borders(Borders::ALL)
+                .border_style(focus_style(pane.focused, theme)),
         )
-    } else {
-      issue_details = Table::new(vec![
-          Row::new(vec![Cell::from("Number")])
-          .style(Style::default().fg(Color::LightCyan))
-          .height(1),
-          Row::new(vec![
-              Cell::from(selected_issue.number.to_string()),
-              Cell::from(selected_issue.title.clone()),
-              Cell::from(selected_issue.labels.iter().map(|l| l.name.as_str()).collect::<Vec<_>>().join(", ")),
-              Cell::from(selected_issue.state.clone()),
-          ])
-          .style(Style::default().fg(Color::White))
-          .height(2),
-
-          Row::new(vec![Cell::from("Repository")])
-          .style(Style::default().fg(Color::LightCyan))
-          .height(1),
-          Row::new(vec![
-              match &selected_issue.repository {
-                  Some(repository) => Cell::from(repository.to_string()),
-                  None => Cell::from("N/A"),
-              },
-          ])
-          .style(Style::default().fg(Color::White))
-          .height(2),
-
-          Row::new(vec![Cell::from("Organization")])
-          .style(Style::default().fg(Color::LightCyan))
-          .height(1),
-          Row::new(vec![
-              match &selected_issue.organization {
-                  Some(organization) => Cell::from(organization.to_string()),
-                  None => Cell::from("N/A"),
-              },
-          ])
-          .style(Style::default().fg(Color::White))
-          .height(2),
-
-          Row::new(vec![Cell::from("Title")])
-          .style(Style::default().fg(Color::LightCyan))
-          .height(1),
-          Row::new(vec![
-              Cell::from(selected_issue.title.clone()),
-          ])
-          .style(Style::default().fg(Color::White))
-          .height(2),
-
-          Row::new(vec![Cell::from("Labels")])
-          .style(Style::default().fg(Color::LightCyan))
-          .height(1),
-          Row::new(vec![
-              Cell::from(selected_issue.labels.iter().map(|l| l.name.as_str()).collect::<Vec<_>>().join(", ")),
-          ])
-          .style(Style::default().fg(Color::White))
-          .height(2),
-
-          Row::new(vec![Cell::from("Description")])
-          .style(Style::default().fg(Color::LightCyan))
-          .height(1),
-          Row::new(vec![
-              match &selected_issue.body {
-                  Some(body) => {
-                      let wrapped_body = wrap(body, body_width).join("\n");
-                      Cell::from(wrapped_body)
-                  }
-                  None => Cell::from("N/A"),
-              },
-          ])
-          .style(Style::default().fg(Color::White))
-          .height(body_height.try_into().unwrap()),
-
-          Row::new(vec![Cell::from("Created at")])
-          .style(Style::default().fg(Color::LightCyan))
-          .height(1),
-          Row::new(vec![
-              Cell::from(selected_issue.created_at.clone()),
-          ])
-          .style(Style::default().fg(Color::White))
-          .height(2),
-
-          Row::new(vec![Cell::from("Updated at")])
-          .style(Style::default().fg(Color::LightCyan))
-          .height(1),
-          Row::new(vec![
-              Cell::from(selected_issue.updated_at.clone()),
-          ])
-          .style(Style::default().fg(Color::White))
-          .height(2),
-      ])
-      .block(
-          Block::default()
-              .title("Details")
-              .border_type(BorderType::Rounded)
-              .borders(Borders::ALL),
-      )
-      .widths(&[Constraint::Min(0)])
-      .highlight_style(
-          Style::default()
-              .add_modifier(Modifier::BOLD)
-              .fg(Color::LightMagenta),
-      )
-    }
+        .style(Style::default().fg(theme.text))
+        .scroll((pane.scroll, 0));
   (issue_list, issue_details)
 }
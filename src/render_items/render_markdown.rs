@@ -0,0 +1,338 @@
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag};
+use textwrap::core::display_width;
+use tui::{
+  style::{Modifier, Style},
+  text::{Span, Spans},
+};
+use crate::structs::Theme;
+
+// Ordered lists keep the number of their next item, bullet lists have none
+struct List {
+  next_number: Option<u64>,
+  indent: usize,
+}
+
+struct MarkdownRenderer {
+  width: usize,
+  lines: Vec<Spans<'static>>,
+  // Spans of the paragraph being built, wrapped when it is flushed
+  current: Vec<Span<'static>>,
+  styles: Vec<Style>,
+  lists: Vec<List>,
+  // Bullet or number of the item whose first line is not flushed yet
+  bullet: Option<String>,
+  quote_depth: usize,
+  code_block: bool,
+  link: Option<(String, String)>,
+  html_comment: bool,
+  theme: Theme,
+}
+
+impl MarkdownRenderer {
+  fn style(&self) -> Style {
+      self.styles.last().copied().unwrap_or_default()
+  }
+
+  fn push_style(&mut self, f: impl Fn(Style) -> Style) {
+      let style = f(self.style());
+      self.styles.push(style);
+  }
+
+  fn push_text(&mut self, text: &str) {
+      if let Some((_, link_text)) = self.link.as_mut() {
+          link_text.push_str(text);
+      }
+      self.current.push(Span::styled(text.to_string(), self.style()));
+  }
+
+  fn quote_prefix(&self) -> Span<'static> {
+      Span::styled("│ ".repeat(self.quote_depth), Style::default().fg(self.theme.muted))
+  }
+
+  fn indent(&self) -> usize {
+      self.lists.last().map(|list| list.indent).unwrap_or(0)
+  }
+
+  // Never two blank lines in a row, quote bars alone count as blank
+  fn blank_line(&mut self) {
+      let blank = |line: &Spans| line.0.iter().all(|span| span.content.chars().all(|c| c.is_whitespace() || c == '│'));
+      if self.lines.last().map(|line| !blank(line)).unwrap_or(false) {
+          self.lines.push(Spans::from(self.quote_prefix()));
+      }
+  }
+
+  // Wrap the pending spans to the width, under the quote bars and list indentation
+  fn flush(&mut self) {
+      if self.current.is_empty() && self.bullet.is_none() {
+          return;
+      }
+      let spans = std::mem::take(&mut self.current);
+      let continuation = " ".repeat(self.indent());
+      let first = self.bullet.take().unwrap_or_else(|| continuation.clone());
+      let prefix_width = display_width(&first).max(continuation.len()) + 2 * self.quote_depth;
+      let available = self.width.saturating_sub(prefix_width).max(10);
+
+      let mut line: Vec<Span<'static>> = vec![self.quote_prefix(), Span::raw(first)];
+      let mut line_width = 0;
+      for span in spans {
+          for word in span.content.split_inclusive(' ') {
+              let word_width = display_width(word.trim_end());
+              if line_width > 0 && line_width + word_width > available {
+                  self.lines.push(Spans::from(std::mem::take(&mut line)));
+                  line = vec![self.quote_prefix(), Span::raw(continuation.clone())];
+                  line_width = 0;
+              }
+              let word = if line_width == 0 { word.trim_start() } else { word };
+              line_width += display_width(word);
+              line.push(Span::styled(word.to_string(), span.style));
+          }
+      }
+      self.lines.push(Spans::from(line));
+  }
+
+  fn start(&mut self, tag: Tag) {
+      match tag {
+          Tag::Paragraph => {}
+          Tag::Heading(level, _, _) => {
+              self.flush();
+              let accent = self.theme.accent;
+              self.push_style(|style| match level {
+                  HeadingLevel::H1 => style.fg(accent).add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+                  _ => style.fg(accent).add_modifier(Modifier::BOLD),
+              });
+          }
+          Tag::BlockQuote => {
+              self.flush();
+              self.quote_depth += 1;
+              let secondary = self.theme.secondary;
+              self.push_style(|style| style.fg(secondary).add_modifier(Modifier::ITALIC));
+          }
+          Tag::CodeBlock(kind) => {
+              self.flush();
+              self.code_block = true;
+              if let CodeBlockKind::Fenced(lang) = kind {
+                  if !lang.is_empty() {
+                      self.lines.push(Spans::from(vec![
+                          self.quote_prefix(),
+                          Span::raw(" ".repeat(self.indent())),
+                          Span::styled(format!("┌ {}", lang), Style::default().fg(self.theme.muted)),
+                      ]));
+                  }
+              }
+          }
+          Tag::List(start) => {
+              self.flush();
+              let indent = self.indent() + if start.is_some() { 3 } else { 2 };
+              self.lists.push(List { next_number: start, indent });
+          }
+          Tag::Item => {
+              self.flush();
+              let parent = self.lists.len().checked_sub(2).map(|i| self.lists[i].indent).unwrap_or(0);
+              if let Some(list) = self.lists.last_mut() {
+                  let marker = match list.next_number.as_mut() {
+                      Some(number) => {
+                          *number += 1;
+                          format!("{}.", *number - 1)
+                      }
+                      None => "•".to_string(),
+                  };
+                  self.bullet = Some(format!("{}{: <width$}", " ".repeat(parent), marker, width = list.indent - parent));
+              }
+          }
+          Tag::Emphasis => self.push_style(|style| style.add_modifier(Modifier::ITALIC)),
+          Tag::Strong => self.push_style(|style| style.add_modifier(Modifier::BOLD)),
+          Tag::Strikethrough => self.push_style(|style| style.add_modifier(Modifier::CROSSED_OUT)),
+          Tag::Link(_, url, _) | Tag::Image(_, url, _) => {
+              self.link = Some((url.to_string(), String::new()));
+              let link = self.theme.link;
+              self.push_style(|style| style.fg(link).add_modifier(Modifier::UNDERLINED));
+          }
+          Tag::Table(_) | Tag::TableHead | Tag::TableRow => self.flush(),
+          Tag::TableCell => {
+              if !self.current.is_empty() {
+                  self.current.push(Span::styled(" │ ", Style::default().fg(self.theme.muted)));
+              }
+          }
+          Tag::FootnoteDefinition(_) => {}
+      }
+  }
+
+  fn end(&mut self, tag: Tag) {
+      match tag {
+          Tag::Paragraph => {
+              self.flush();
+              if self.lists.is_empty() {
+                  self.blank_line();
+              }
+          }
+          Tag::Heading(..) => {
+              self.styles.pop();
+              self.flush();
+              self.blank_line();
+          }
+          Tag::BlockQuote => {
+              self.flush();
+              self.styles.pop();
+              self.quote_depth -= 1;
+              self.blank_line();
+          }
+          Tag::CodeBlock(_) => {
+              self.code_block = false;
+              self.blank_line();
+          }
+          Tag::List(_) => {
+              self.flush();
+              self.lists.pop();
+              if self.lists.is_empty() {
+                  self.blank_line();
+              }
+          }
+          Tag::Item => self.flush(),
+          Tag::Emphasis | Tag::Strong | Tag::Strikethrough => {
+              self.styles.pop();
+          }
+          Tag::Link(..) | Tag::Image(..) => {
+              self.styles.pop();
+              // Bare links already show their url, others get it after their text
+              if let Some((url, text)) = self.link.take() {
+                  if url != text && !url.starts_with('#') {
+                      self.current.push(Span::styled(format!(" ({})", url), Style::default().fg(self.theme.muted)));
+                  }
+              }
+          }
+          Tag::TableCell => {}
+          Tag::TableHead => {
+              let width = self.current.iter().map(|span| span.width()).sum::<usize>().min(self.width);
+              self.current.iter_mut().for_each(|span| span.style = span.style.add_modifier(Modifier::BOLD));
+              self.flush();
+              self.lines.push(Spans::from(Span::styled("─".repeat(width), Style::default().fg(self.theme.muted))));
+          }
+          Tag::TableRow => self.flush(),
+          Tag::Table(_) => self.blank_line(),
+          Tag::FootnoteDefinition(_) => self.flush(),
+      }
+  }
+
+  fn code_block_text(&mut self, text: &str) {
+      let style = Style::default().fg(self.theme.code);
+      for line in text.lines() {
+          self.lines.push(Spans::from(vec![
+              self.quote_prefix(),
+              Span::raw(" ".repeat(self.indent())),
+              Span::styled("│ ", Style::default().fg(self.theme.muted)),
+              Span::styled(line.replace('\t', "    "), style),
+          ]));
+      }
+  }
+}
+
+// Markdown of issue bodies and comments as styled lines wrapped to the given width
+pub fn render_markdown(text: &str, width: usize, theme: &Theme) -> Vec<Spans<'static>> {
+  let mut renderer = MarkdownRenderer {
+      width,
+      lines: vec![],
+      current: vec![],
+      styles: vec![Style::default()],
+      lists: vec![],
+      bullet: None,
+      quote_depth: 0,
+      code_block: false,
+      link: None,
+      html_comment: false,
+      theme: *theme,
+  };
+  let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+  for event in Parser::new_ext(text, options) {
+      match event {
+          Event::Start(tag) => renderer.start(tag),
+          Event::End(tag) => renderer.end(tag),
+          Event::Text(text) if renderer.code_block => renderer.code_block_text(&text),
+          Event::Text(text) => renderer.push_text(&text),
+          Event::Code(code) => {
+              renderer.current.push(Span::styled(code.to_string(), Style::default().fg(theme.code)));
+          }
+          // Templates are full of html comments, other html is shown as is
+          Event::Html(html) => {
+              let html = html.trim();
+              if html.starts_with("<!--") || renderer.html_comment {
+                  renderer.html_comment = !html.contains("-->");
+              } else if html.starts_with("<br") {
+                  renderer.flush();
+              } else if !html.is_empty() {
+                  renderer.push_text(html);
+                  renderer.push_text(" ");
+              }
+          }
+          Event::FootnoteReference(name) => renderer.push_text(&format!("[^{}]", name)),
+          Event::SoftBreak => renderer.push_text(" "),
+          Event::HardBreak => renderer.flush(),
+          Event::Rule => {
+              renderer.flush();
+              renderer.lines.push(Spans::from(Span::styled("─".repeat(width.min(40)), Style::default().fg(theme.muted))));
+          }
+          Event::TaskListMarker(checked) => {
+              let (marker, color) = if checked { ("☑ ", theme.success) } else { ("☐ ", theme.secondary) };
+              renderer.current.push(Span::styled(marker, Style::default().fg(color)));
+          }
+      }
+  }
+  renderer.flush();
+  while renderer.lines.last().map(|line| line.0.iter().all(|span| span.content.trim().is_empty())).unwrap_or(false) {
+      renderer.lines.pop();
+  }
+  renderer.lines
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn text(markdown: &str, width: usize) -> Vec<String> {
+      render_markdown(markdown, width, &Theme::dark())
+          .iter()
+          .map(|line| line.0.iter().map(|span| span.content.as_ref()).collect())
+          .collect()
+  }
+
+  #[test]
+  fn lists_are_bulleted_numbered_and_nested() {
+      assert_eq!(
+          text("- one\n- two\n  - nested\n\n1. first\n2. second\n\n- [x] done\n- [ ] todo", 40),
+          vec!["• one", "• two", "  • nested", "", "1. first", "2. second", "", "• ☑ done", "• ☐ todo"]
+      );
+  }
+
+  #[test]
+  fn quotes_are_barred_once_per_level() {
+      let lines = text("> quoted\n> > deeper\n\nafter", 40);
+      assert_eq!(lines[0], "│ quoted");
+      assert!(lines.contains(&"│ │ deeper".to_string()));
+      assert_eq!(lines.last().map(String::as_str), Some("after"));
+  }
+
+  #[test]
+  fn code_fences_keep_their_lines_and_language() {
+      assert_eq!(
+          text("```rust\nfn main() {\n\tloop {}\n}\n```\ntext", 10),
+          vec!["┌ rust", "│ fn main() {", "│     loop {}", "│ }", "", "text"]
+      );
+  }
+
+  #[test]
+  fn table_cells_are_separated_and_the_head_underlined() {
+      assert_eq!(
+          text("| name | b |\n|---|---|\n| x | 22 |", 40),
+          vec!["name │ b", "────────", "x │ 22"]
+      );
+  }
+
+  #[test]
+  fn links_show_their_target_and_comments_are_hidden() {
+      assert_eq!(text("see [docs](https://x.y)<!-- template -->", 40), vec!["see docs (https://x.y)"]);
+  }
+
+  #[test]
+  fn paragraphs_wrap_at_the_width() {
+      assert_eq!(text("a long paragraph that has to wrap", 12), vec!["a long ", "paragraph ", "that has to ", "wrap"]);
+  }
+}
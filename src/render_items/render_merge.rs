@@ -0,0 +1,86 @@
+use tui::{
+    Frame,
+    backend::Backend,
+    widgets::{ Block, Borders, Clear, Paragraph, Wrap },
+    style::{ Modifier, Style },
+    text::{ Span, Spans, Text },
+};
+
+use crate::structs::{ MergeField, MergeForm, Theme, MERGE_METHODS };
+use super::render_input::input_spans;
+use crate::utils::centered_rect;
+
+fn label<'a>(text: &'a str, focused: bool, theme: &Theme) -> Span<'a> {
+    let style = if focused {
+        Style::default().fg(theme.selected).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.accent)
+    };
+    Span::styled(text, style)
+}
+
+pub fn render_merge(rect: &mut Frame<impl Backend>, form: &MergeForm, theme: &Theme) {
+    let (mergeability, _) = form.pull_request.mergeability();
+    let mergeability_color = match form.pull_request.mergeable_state.as_str() {
+        "clean" | "has_hooks" => theme.success,
+        "blocked" => theme.failure,
+        _ => theme.warning,
+    };
+
+    let mut lines: Vec<Spans> = vec![
+        Spans::from(vec![
+            Span::raw(format!("{} into {}: ", form.pull_request.head.label, form.pull_request.base.label)),
+            Span::styled(mergeability, Style::default().fg(mergeability_color)),
+        ]),
+        Spans::from(""),
+    ];
+
+    let mut methods = vec![label("Method: ", form.focus == MergeField::Method, theme)];
+    for (index, method) in MERGE_METHODS.iter().enumerate() {
+        if index == form.method {
+            methods.push(Span::styled(format!("[{}] ", method), Style::default().add_modifier(Modifier::BOLD)));
+        } else {
+            methods.push(Span::raw(format!(" {}  ", method)));
+        }
+    }
+    lines.push(Spans::from(methods));
+    lines.push(Spans::from(""));
+
+    if form.method() == "rebase" {
+        lines.push(Spans::from(Span::styled(
+            "Commits are rebased onto the base branch, no merge commit is created",
+            Style::default().fg(theme.muted),
+        )));
+    } else {
+        lines.push(Spans::from(label("Commit title:", form.focus == MergeField::Title, theme)));
+        lines.extend(input_spans(&form.title, form.focus == MergeField::Title));
+        lines.push(Spans::from(label("Commit message:", form.focus == MergeField::Message, theme)));
+        lines.extend(input_spans(&form.message, form.focus == MergeField::Message));
+    }
+    lines.push(Spans::from(""));
+
+    let delete_label = if form.pull_request.head_in_base_repo() {
+        format!("[{}] Delete branch {}", if form.delete_branch { "x" } else { " " }, form.pull_request.head.branch)
+    } else {
+        "The head branch belongs to a fork and cannot be deleted".to_string()
+    };
+    lines.push(Spans::from(vec![
+        label("Branch: ", form.focus == MergeField::DeleteBranch, theme),
+        Span::raw(delete_label),
+    ]));
+    lines.push(Spans::from(""));
+    lines.push(Spans::from(Span::styled(
+        "TAB: next field - LEFT / RIGHT: method - SPACE: toggle - ENTER: merge - ESC: cancel",
+        Style::default().fg(theme.accent),
+    )));
+
+    let paragraph = Paragraph::new(Text::from(lines))
+        .block(Block::default().borders(Borders::ALL).title(format!("Merge #{}", form.pull_request.number)))
+        .style(theme.popup())
+        .wrap(Wrap { trim: false });
+
+    let popup_chunk = centered_rect(70, 20, rect.size());
+
+    rect.render_widget(Clear, popup_chunk);
+    rect.render_widget(paragraph, popup_chunk);
+}
@@ -0,0 +1,67 @@
+use tui::{
+    Frame,
+    backend::Backend,
+    layout::{ Constraint, Direction, Layout },
+    widgets::{ Block, Borders, Clear, List, ListItem, Paragraph },
+    style::{ Modifier, Style },
+    text::{ Span, Spans },
+};
+
+use crate::structs::{ Action, KeyContext, Keymap, ReactionPicker, Theme, REACTION_KINDS };
+use crate::utils::centered_rect;
+
+pub fn render_reactions(rect: &mut Frame<impl Backend>, picker: &mut ReactionPicker, keymap: &Keymap, theme: &Theme) {
+    let emojis: Vec<Span> = REACTION_KINDS
+        .iter()
+        .enumerate()
+        .map(|(index, (_, emoji))| {
+            if index == picker.emoji {
+                Span::styled(format!("[{}]", emoji), Style::default().fg(theme.selected).add_modifier(Modifier::BOLD))
+            } else {
+                Span::raw(format!(" {} ", emoji))
+            }
+        })
+        .collect();
+
+    let summary = |reactions: Option<String>| {
+        Span::styled(
+            reactions.filter(|summary| !summary.is_empty()).map(|summary| format!("  {}", summary)).unwrap_or_default(),
+            Style::default().fg(theme.secondary),
+        )
+    };
+    let mut targets = vec![ListItem::new(Spans::from(vec![
+        Span::raw(format!("#{} {}", picker.item.number, picker.item.title)),
+        summary(picker.item.reactions.as_ref().map(|r| r.summary())),
+    ]))];
+    targets.extend(picker.item.comments_list.iter().map(|comment| {
+        let first_line: String = comment.body.lines().next().unwrap_or("").chars().take(60).collect();
+        ListItem::new(Spans::from(vec![
+            Span::raw(format!("  {}: {}", comment.user.login, first_line)),
+            summary(comment.reactions.as_ref().map(|r| r.summary())),
+        ]))
+    }));
+
+    let popup_chunk = centered_rect(90, 20, rect.size());
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
+        .split(popup_chunk);
+
+    let emoji_hint = keymap.hint(
+        &[KeyContext::Global],
+        &[(Action::MoveLeft, "previous emoji"), (Action::MoveRight, "next emoji"), (Action::Confirm, "send"), (Action::Cancel, "close")],
+    );
+    let target_hint = keymap.hint(&[KeyContext::Global], &[(Action::MoveUp, "previous"), (Action::MoveDown, "next")]);
+    let emoji_bar = Paragraph::new(Spans::from(emojis))
+        .block(Block::default().borders(Borders::ALL).title(format!("React - {}", emoji_hint)))
+        .style(theme.popup());
+    let list = List::new(targets)
+        .block(Block::default().borders(Borders::ALL).title(format!("On - {}", target_hint)))
+        .style(theme.popup())
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD).fg(theme.selected))
+        .highlight_symbol(">> ");
+
+    rect.render_widget(Clear, popup_chunk);
+    rect.render_widget(emoji_bar, chunks[0]);
+    rect.render_stateful_widget(list, chunks[1], &mut picker.target_state);
+}
@@ -0,0 +1,59 @@
+use tui::{
+    Frame,
+    backend::Backend,
+    widgets::{ Block, Borders, Clear, List, ListItem },
+    style::{ Modifier, Style },
+    text::{ Span, Spans },
+};
+
+use crate::structs::{ Action, KeyContext, Keymap, Theme, TimelineView };
+use crate::utils::{ centered_rect, format_age };
+
+pub fn render_timeline(rect: &mut Frame<impl Backend>, view: &mut TimelineView, keymap: &Keymap, theme: &Theme) {
+    let items: Vec<ListItem> = if view.events.is_empty() {
+        vec![ListItem::new("Nothing happened on this item yet")]
+    } else {
+        view.events
+            .iter()
+            .map(|event| {
+                // Linked pull requests and issues can be jumped to, they stand out
+                let style = match (event.linked_issue(), event.event.as_str()) {
+                    (Some(issue), _) if issue.pull_request.is_some() => Style::default().fg(theme.special),
+                    (Some(_), _) => Style::default().fg(theme.link),
+                    (None, "labeled") | (None, "unlabeled") => Style::default()
+                        .fg(event.label.as_ref().map(|label| label.background()).unwrap_or(theme.popup_fg)),
+                    (None, "closed") => Style::default().fg(theme.failure),
+                    (None, "reopened") => Style::default().fg(theme.success),
+                    _ => Style::default().fg(theme.popup_fg),
+                };
+                ListItem::new(Spans::from(vec![
+                    Span::styled(format!("{: >4} ", format_age(event.date())), Style::default().fg(theme.secondary)),
+                    Span::raw(format!("{} ", event.icon())),
+                    Span::styled(event.description(), style),
+                    Span::styled(
+                        if event.link().is_some() { "  ↪" } else { "" },
+                        Style::default().fg(theme.accent),
+                    ),
+                ]))
+            })
+            .collect()
+    };
+
+    let title = format!(
+        "Timeline of #{} {} - {}",
+        view.item.number,
+        view.item.title,
+        keymap.hint(&[KeyContext::Global], &[(Action::Confirm, "go to the linked item"), (Action::Cancel, "close")])
+    );
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .style(theme.popup())
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD))
+        .highlight_symbol(">> ");
+
+    let size = rect.size();
+    let popup_chunk = centered_rect(size.width.saturating_sub(8), size.height.saturating_sub(6), size);
+
+    rect.render_widget(Clear, popup_chunk);
+    rect.render_stateful_widget(list, popup_chunk, &mut view.state);
+}
@@ -1,12 +1,15 @@
 use tui::{
     layout::Alignment,
-    style::{ Modifier, Style, Color },
+    style::{ Modifier, Style },
     text::Span,
     widgets::{ Block, BorderType, Borders, Paragraph },
 };
 
+use crate::structs::Theme;
+
 pub fn render_waiting_screen<B: tui::backend::Backend>(
-    terminal: &mut tui::Terminal<B>
+    terminal: &mut tui::Terminal<B>,
+    theme: &Theme
 ) -> Result<(), Box<dyn std::error::Error>> {
     let loading_text =
         r#"
@@ -35,7 +38,7 @@ pub fn render_waiting_screen<B: tui::backend::Backend>(
                     Span::styled("GitHub Assistant", Style::default().add_modifier(Modifier::BOLD))
                 )
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::White).bg(Color::DarkGray))
+                .border_style(theme.popup())
                 .border_type(BorderType::Rounded)
         );
 
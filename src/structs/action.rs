@@ -0,0 +1,254 @@
+use super::KeyContext;
+
+// What a key does once the keymap has resolved it, the context decides how it is carried out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    Home,
+    Assignments,
+    Closed,
+    ToReview,
+    Authored,
+    Mentions,
+    Inbox,
+    NextTab,
+    PreviousTab,
+    Refresh,
+    Search,
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+    Confirm,
+    Cancel,
+    ToggleFocus,
+    ToggleHidden,
+    Actions,
+    SaveSearch,
+    FuzzyFilter,
+    Sort,
+    SortOrder,
+    Group,
+    ToggleGroup,
+    AddFilters,
+    FilterPullRequests,
+    FilterIssues,
+    RemoveFilter,
+    ClearFilters,
+    Diff,
+    Merge,
+    Checks,
+    React,
+    Timeline,
+    ExpandFile,
+    Comment,
+    AddToReview,
+    SubmitReview,
+    MarkRead,
+    MarkDone,
+    Unsubscribe,
+    Help,
+    Priority,
+}
+
+pub const ACTIONS: [Action; 50] = [
+    Action::Quit,
+    Action::Home,
+    Action::Assignments,
+    Action::Closed,
+    Action::ToReview,
+    Action::Authored,
+    Action::Mentions,
+    Action::Inbox,
+    Action::NextTab,
+    Action::PreviousTab,
+    Action::Refresh,
+    Action::Search,
+    Action::MoveUp,
+    Action::MoveDown,
+    Action::MoveLeft,
+    Action::MoveRight,
+    Action::PageUp,
+    Action::PageDown,
+    Action::Top,
+    Action::Bottom,
+    Action::Confirm,
+    Action::Cancel,
+    Action::ToggleFocus,
+    Action::ToggleHidden,
+    Action::Actions,
+    Action::SaveSearch,
+    Action::FuzzyFilter,
+    Action::Sort,
+    Action::SortOrder,
+    Action::Group,
+    Action::ToggleGroup,
+    Action::AddFilters,
+    Action::FilterPullRequests,
+    Action::FilterIssues,
+    Action::RemoveFilter,
+    Action::ClearFilters,
+    Action::Diff,
+    Action::Merge,
+    Action::Checks,
+    Action::React,
+    Action::Timeline,
+    Action::ExpandFile,
+    Action::Comment,
+    Action::AddToReview,
+    Action::SubmitReview,
+    Action::MarkRead,
+    Action::MarkDone,
+    Action::Unsubscribe,
+    Action::Help,
+    Action::Priority,
+];
+
+impl Action {
+    // Name of the action in the [keys] section of the config
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Home => "home",
+            Action::Assignments => "assignments",
+            Action::Closed => "closed",
+            Action::ToReview => "to_review",
+            Action::Authored => "authored",
+            Action::Mentions => "mentions",
+            Action::Inbox => "inbox",
+            Action::NextTab => "next_tab",
+            Action::PreviousTab => "previous_tab",
+            Action::Refresh => "refresh",
+            Action::Search => "search",
+            Action::MoveUp => "up",
+            Action::MoveDown => "down",
+            Action::MoveLeft => "left",
+            Action::MoveRight => "right",
+            Action::PageUp => "page_up",
+            Action::PageDown => "page_down",
+            Action::Top => "top",
+            Action::Bottom => "bottom",
+            Action::Confirm => "confirm",
+            Action::Cancel => "cancel",
+            Action::ToggleFocus => "toggle_focus",
+            Action::ToggleHidden => "toggle_hidden",
+            Action::Actions => "actions",
+            Action::SaveSearch => "save_search",
+            Action::FuzzyFilter => "fuzzy_filter",
+            Action::Sort => "sort",
+            Action::SortOrder => "sort_order",
+            Action::Group => "group",
+            Action::ToggleGroup => "toggle_group",
+            Action::AddFilters => "add_filters",
+            Action::FilterPullRequests => "filter_pull_requests",
+            Action::FilterIssues => "filter_issues",
+            Action::RemoveFilter => "remove_filter",
+            Action::ClearFilters => "clear_filters",
+            Action::Diff => "diff",
+            Action::Merge => "merge",
+            Action::Checks => "checks",
+            Action::React => "react",
+            Action::Timeline => "timeline",
+            Action::ExpandFile => "expand_file",
+            Action::Comment => "comment",
+            Action::AddToReview => "add_to_review",
+            Action::SubmitReview => "submit_review",
+            Action::MarkRead => "mark_read",
+            Action::MarkDone => "mark_done",
+            Action::Unsubscribe => "unsubscribe",
+            Action::Help => "help",
+            Action::Priority => "priority",
+        }
+    }
+
+    // What the action does on the screens where it is bound, shown by the help
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::Quit => "Quit the app",
+            Action::Home => "Show the home screen",
+            Action::Assignments => "Show the issues assigned to you",
+            Action::Closed => "Show your closed issues",
+            Action::ToReview => "Show the pull requests to review",
+            Action::Authored => "Show the pull requests you authored",
+            Action::Mentions => "Show the threads mentioning you",
+            Action::Inbox => "Show the notifications inbox",
+            Action::NextTab => "Next tab, saved searches included",
+            Action::PreviousTab => "Previous tab",
+            Action::Refresh => "Reload all the tabs and the notifications",
+            Action::Search => "Search issues and pull requests with GitHub qualifiers",
+            Action::MoveUp => "Move up, or scroll the details up when they have the focus",
+            Action::MoveDown => "Move down, or scroll the details down when they have the focus",
+            Action::MoveLeft => "Show the details of the selected item, or focus the files of the diff",
+            Action::MoveRight => "Show the comments of the selected item, or focus the patch of the diff",
+            Action::PageUp => "Scroll the details, or the patch of the diff, a page up",
+            Action::PageDown => "Scroll the details, or the patch of the diff, a page down",
+            Action::Top => "First item, or top of the details when they have the focus",
+            Action::Bottom => "Last item, or bottom of the details when they have the focus",
+            Action::Confirm => "Open the selected item in the browser",
+            Action::Cancel => "Close the diff or the popup",
+            Action::ToggleFocus => "Move the focus between the list and the details",
+            Action::ToggleHidden => "Show or hide the comments of ignored authors",
+            Action::Actions => "Show the actions: close, filter on an organisation, a repository or a label",
+            Action::SaveSearch => "Save the search as a tab",
+            Action::FuzzyFilter => "Fuzzy filter the list as you type",
+            Action::Sort => "Next sort mode",
+            Action::SortOrder => "Toggle ascending / descending order",
+            Action::Group => "Group by repository, organization or not at all",
+            Action::ToggleGroup => "Collapse or expand the group of the selected item",
+            Action::AddFilters => "Add filters: org, repo, is, label, author, age",
+            Action::FilterPullRequests => "Keep only pull requests",
+            Action::FilterIssues => "Keep only issues",
+            Action::RemoveFilter => "Remove one of the active filters",
+            Action::ClearFilters => "Clear all filters",
+            Action::Diff => "Show the diff of the selected pull request",
+            Action::Merge => "Merge the pull request",
+            Action::Checks => "Show the CI checks of the pull request",
+            Action::React => "React to the selected item or one of its comments",
+            Action::Timeline => "Show the timeline of the selected item",
+            Action::ExpandFile => "Expand or collapse a large file",
+            Action::Comment => "Comment on the selected line",
+            Action::AddToReview => "Add a comment on the selected line to the pending review",
+            Action::SubmitReview => "Submit the pending review",
+            Action::MarkRead => "Mark the notification as read",
+            Action::MarkDone => "Mark the notification as done",
+            Action::Unsubscribe => "Unsubscribe from the thread",
+            Action::Help => "Show the keys of the current screen",
+            Action::Priority => "Set the local priority of the selected item: P1, P2, P3, then none",
+        }
+    }
+
+    // What the action does on the screens of the context, some screens give it a more specific meaning.
+    // None when the screen ignores it, e.g. the moves on the home screen
+    pub fn description_in(&self, context: KeyContext) -> Option<&'static str> {
+        let description = match (context, self) {
+            (KeyContext::Global, Action::MoveUp | Action::MoveDown | Action::MoveLeft | Action::MoveRight) => return None,
+            (KeyContext::Global, Action::PageUp | Action::PageDown | Action::Top | Action::Bottom) => return None,
+            (KeyContext::Global, Action::Confirm | Action::Cancel) => return None,
+            (KeyContext::Diff, Action::MoveUp) => "Previous file, or line of the patch when it has the focus",
+            (KeyContext::Diff, Action::MoveDown) => "Next file, or line of the patch when it has the focus",
+            (KeyContext::Diff, Action::Top) => "First file, or line of the patch when it has the focus",
+            (KeyContext::Diff, Action::Bottom) => "Last file, or line of the patch when it has the focus",
+            (KeyContext::Diff, Action::PageUp) => "Move a page up in the patch",
+            (KeyContext::Diff, Action::PageDown) => "Move a page down in the patch",
+            (KeyContext::Diff, Action::MoveLeft) => "Focus the files",
+            (KeyContext::Diff, Action::MoveRight) => "Focus the patch",
+            (KeyContext::Diff, Action::Confirm) => "Open the files of the pull request in the browser",
+            (KeyContext::Diff, Action::Cancel) => "Close the diff",
+            (KeyContext::Inbox, Action::MoveUp) => "Previous notification",
+            (KeyContext::Inbox, Action::MoveDown) => "Next notification",
+            (KeyContext::Inbox, Action::Confirm) => "Open the notification in the browser and mark it as read",
+            (KeyContext::Inbox, Action::MoveLeft | Action::MoveRight | Action::PageUp | Action::PageDown) => return None,
+            (KeyContext::Inbox, Action::Top | Action::Bottom | Action::Cancel) => return None,
+            _ => self.description(),
+        };
+        Some(description)
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        ACTIONS.iter().copied().find(|action| action.name() == name)
+    }
+}
@@ -1,4 +1,4 @@
-use super::{IssueComments, Label};
+use super::{CiSummary, IssueComments, Label, PullRequest, Reactions, User};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -12,6 +12,12 @@ pub struct ApiResponseItem {
     pub updated_at: String,
     pub labels: Vec<Label>,
     pub body: Option<String>,
+    #[serde(default)]
+    pub user: Option<User>,
+    #[serde(default)]
+    pub comments: i32,
+    #[serde(default)]
+    pub reactions: Option<Reactions>,
     pub repository: Option<String>,
     pub organization: Option<String>,
     #[serde(rename = "comments_url")]
@@ -20,4 +26,54 @@ pub struct ApiResponseItem {
     pub comments_list: Vec<IssueComments>,
     #[serde(skip_deserializing)]
     pub is_pr: bool,
+    #[serde(skip_deserializing)]
+    pub ci_status: Option<CiSummary>,
+    #[serde(skip_deserializing)]
+    pub pr_details: Option<PullRequest>,
+}
+
+impl ApiResponseItem {
+    // Count a reaction the user just left on the item, or on one of its comments
+    pub fn add_reaction(&mut self, comment_id: Option<u64>, content: &str) {
+        match comment_id {
+            Some(id) => {
+                if let Some(comment) = self.comments_list.iter_mut().find(|comment| comment.id == id) {
+                    comment.add_reaction(content);
+                }
+            }
+            None => self.reactions.get_or_insert_with(Reactions::default).add(content),
+        }
+    }
+}
+
+#[cfg(test)]
+impl ApiResponseItem {
+    // Open issue of org/app without labels, comments or body, tests set the fields they look at
+    pub fn sample(number: i32, title: &str) -> ApiResponseItem {
+        ApiResponseItem {
+            url: format!("https://github.com/org/app/issues/{}", number),
+            title: title.to_string(),
+            number,
+            state: "open".to_string(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+            labels: vec![],
+            body: None,
+            user: None,
+            comments: 0,
+            reactions: None,
+            repository: Some("app".to_string()),
+            organization: Some("org".to_string()),
+            comments_url: String::new(),
+            comments_list: vec![],
+            is_pr: false,
+            ci_status: None,
+            pr_details: None,
+        }
+    }
+
+    pub fn with_labels(mut self, labels: &[&str]) -> ApiResponseItem {
+        self.labels = labels.iter().map(|name| Label { name: name.to_string(), color: String::new(), description: None }).collect();
+        self
+    }
 }
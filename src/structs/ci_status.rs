@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CommitStatus {
+    pub context: String,
+    pub state: String,
+    pub description: Option<String>,
+    pub target_url: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CombinedStatus {
+    pub state: String,
+    pub statuses: Vec<CommitStatus>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CheckRun {
+    pub name: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+    pub started_at: Option<String>,
+    pub completed_at: Option<String>,
+    pub html_url: Option<String>,
+    pub details_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CheckRuns {
+    pub total_count: i32,
+    pub check_runs: Vec<CheckRun>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+pub enum CiState {
+    Success,
+    Failure,
+    Pending,
+    Neutral,
+}
+
+impl CiState {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            CiState::Success => "✅",
+            CiState::Failure => "❌",
+            CiState::Pending => "⏳",
+            CiState::Neutral => "⚪",
+        }
+    }
+}
+
+// A commit status or a check run, whichever API it comes from
+#[derive(Debug, Serialize, Clone)]
+pub struct CiCheck {
+    pub name: String,
+    pub state: CiState,
+    pub conclusion: String,
+    pub duration: Option<i64>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CiSummary {
+    pub sha: String,
+    pub state: CiState,
+    pub checks: Vec<CiCheck>,
+}
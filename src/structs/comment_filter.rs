@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::utils::glob_match;
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct RepoCommentFilter {
+    pub ignore: Vec<String>,
+}
+
+// Authors whose comments are hidden, e.g. deploy previews and other bots.
+// A repository listed under `repos` ("org/repo") uses its own list instead of the global one
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CommentFilter {
+    #[serde(default = "default_ignore")]
+    pub ignore: Vec<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub repos: HashMap<String, RepoCommentFilter>,
+}
+
+fn default_ignore() -> Vec<String> {
+    vec!["netlify[bot]".to_string(), "gatsby-cloud[bot]".to_string()]
+}
+
+impl Default for CommentFilter {
+    fn default() -> CommentFilter {
+        CommentFilter { ignore: default_ignore(), repos: HashMap::new() }
+    }
+}
+
+impl CommentFilter {
+    pub fn hides(&self, organization: &str, repository: &str, login: &str) -> bool {
+        let patterns = match self.repos.get(&format!("{}/{}", organization, repository)) {
+            Some(repo) => &repo.ignore,
+            None => &self.ignore,
+        };
+        patterns.iter().any(|pattern| glob_match(pattern, login))
+    }
+}
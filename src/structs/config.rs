@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use std::{error::Error, fs, path::PathBuf};
+use toml_edit::{value, ArrayOfTables, DocumentMut, Item, Table, Value};
+
+use super::{CommentFilter, KeysConfig, Staleness, ThemeConfig};
+
+// A named tab running an arbitrary GitHub issue search, e.g. "repo:org/x label:bug no:assignee"
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct SavedSearch {
+    pub name: String,
+    pub query: String,
+}
+
+// User settings read from config.toml
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct Config {
+    #[serde(default)]
+    pub tabs: Vec<SavedSearch>,
+    #[serde(default)]
+    pub comments: CommentFilter,
+    #[serde(default)]
+    pub staleness: Staleness,
+    #[serde(default, skip_serializing_if = "ThemeConfig::is_empty")]
+    pub theme: ThemeConfig,
+    #[serde(default, skip_serializing_if = "KeysConfig::is_empty")]
+    pub keys: KeysConfig,
+}
+
+impl Config {
+    // GITHUB_ASSISTANT_CONFIG when set, otherwise github-assistant/config.toml in the user config directory
+    pub fn path() -> Option<PathBuf> {
+        match std::env::var("GITHUB_ASSISTANT_CONFIG") {
+            Ok(path) => Some(PathBuf::from(path)),
+            Err(_) => dirs::config_dir().map(|dir| dir.join("github-assistant").join("config.toml")),
+        }
+    }
+
+    // A missing file is an empty config, an invalid one is an error
+    pub fn load() -> Result<Config, Box<dyn Error>> {
+        let path = match Config::path() {
+            Some(path) if path.exists() => path,
+            _ => return Ok(Config::default()),
+        };
+        let content = fs::read_to_string(&path)?;
+        let config: Config = toml::from_str(&content).map_err(|e| format!("Invalid config {}: {}", path.display(), e))?;
+        config.staleness.validate()?;
+        Ok(config)
+    }
+
+    // Append the search to the [[tabs]] of the file, leaving the rest of it as the user wrote it
+    pub fn save_search(search: &SavedSearch) -> Result<(), Box<dyn Error>> {
+        let path = Config::path().ok_or("No config directory found")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let content = if path.exists() { fs::read_to_string(&path)? } else { String::new() };
+        let content = append_search(&content, search).map_err(|e| format!("Invalid config {}: {}", path.display(), e))?;
+        fs::write(&path, content)?;
+        Ok(())
+    }
+}
+
+// Content of config.toml with the search added to its tabs, comments and formatting untouched
+fn append_search(content: &str, search: &SavedSearch) -> Result<String, String> {
+    let mut document: DocumentMut = content.parse().map_err(|e| format!("{}", e))?;
+    let mut entry = Table::new();
+    entry.insert("name", value(&search.name));
+    entry.insert("query", value(&search.query));
+    match document.entry("tabs").or_insert(Item::ArrayOfTables(ArrayOfTables::new())) {
+        Item::ArrayOfTables(tabs) => tabs.push(entry),
+        // tabs = [{ name = "...", query = "..." }]
+        Item::Value(Value::Array(tabs)) => tabs.push(entry.into_inline_table()),
+        _ => return Err("tabs is not a list of tables".to_string()),
+    }
+    Ok(document.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search() -> SavedSearch {
+        SavedSearch { name: "Bugs".to_string(), query: "label:bug".to_string() }
+    }
+
+    #[test]
+    fn appending_keeps_comments_and_existing_tabs() {
+        let content = "# My tabs\n[[tabs]]\nname = \"Mine\" # first one\nquery = \"author:me\"\n\n[staleness]\nwarning_days = 30\n";
+        let saved = append_search(content, &search()).unwrap();
+        assert!(saved.starts_with("# My tabs\n[[tabs]]\nname = \"Mine\" # first one\n"));
+        assert!(saved.contains("[staleness]\nwarning_days = 30"));
+        let config: Config = toml::from_str(&saved).unwrap();
+        let tabs: Vec<(&str, &str)> = config.tabs.iter().map(|tab| (tab.name.as_str(), tab.query.as_str())).collect();
+        assert_eq!(tabs, vec![("Mine", "author:me"), ("Bugs", "label:bug")]);
+        assert_eq!(config.staleness.warning_days, 30);
+    }
+
+    #[test]
+    fn appending_to_an_empty_or_inline_config() {
+        let config: Config = toml::from_str(&append_search("", &search()).unwrap()).unwrap();
+        assert_eq!(config.tabs.len(), 1);
+
+        let inline = "tabs = [{ name = \"Mine\", query = \"author:me\" }]\n";
+        let config: Config = toml::from_str(&append_search(inline, &search()).unwrap()).unwrap();
+        assert_eq!(config.tabs.iter().map(|tab| tab.name.as_str()).collect::<Vec<_>>(), vec!["Mine", "Bugs"]);
+
+        assert!(append_search("tabs = 3\n", &search()).is_err());
+    }
+}
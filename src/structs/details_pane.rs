@@ -0,0 +1,101 @@
+// Scroll position of the details pane on the right of the lists, and whether it has the focus
+#[derive(Default)]
+pub struct DetailsPane {
+    pub focused: bool,
+    pub scroll: u16,
+    // Size of the content and of the viewport, known once the pane has been rendered
+    pub line_count: u16,
+    pub height: u16,
+    // Item the pane shows, the scroll goes back to the top when it changes
+    pub item_url: String,
+    // Show the comments the ignore list of the config hides
+    pub show_hidden: bool,
+}
+
+impl DetailsPane {
+    fn max_scroll(&self) -> u16 {
+        self.line_count.saturating_sub(self.height)
+    }
+
+    pub fn scroll_by(&mut self, delta: i32) {
+        let scroll = (self.scroll as i32 + delta).clamp(0, self.max_scroll() as i32);
+        self.scroll = scroll as u16;
+    }
+
+    pub fn page(&mut self, direction: i32) {
+        self.scroll_by(direction * self.height.saturating_sub(1).max(1) as i32);
+    }
+
+    pub fn top(&mut self) {
+        self.scroll = 0;
+    }
+
+    pub fn bottom(&mut self) {
+        self.scroll = self.max_scroll();
+    }
+
+    // Record what the pane shows once its content is known, keeping the scroll in range
+    pub fn update(&mut self, item_url: &str, line_count: usize, height: u16) {
+        if self.item_url != item_url {
+            self.item_url = item_url.to_string();
+            self.scroll = 0;
+        }
+        self.line_count = line_count.min(u16::MAX as usize) as u16;
+        self.height = height;
+        self.scroll = self.scroll.min(self.max_scroll());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pane(line_count: usize, height: u16) -> DetailsPane {
+        let mut pane = DetailsPane::default();
+        pane.update("https://github.com/org/app/issues/1", line_count, height);
+        pane
+    }
+
+    #[test]
+    fn scrolling_stops_at_the_last_page() {
+        let mut pane = pane(30, 10);
+        pane.scroll_by(100);
+        assert_eq!(pane.scroll, 20);
+        pane.page(1);
+        assert_eq!(pane.scroll, 20);
+        pane.top();
+        pane.bottom();
+        assert_eq!(pane.scroll, 20);
+    }
+
+    #[test]
+    fn scrolling_up_stops_at_the_top() {
+        let mut pane = pane(30, 10);
+        pane.page(-1);
+        assert_eq!(pane.scroll, 0);
+        pane.page(1);
+        assert_eq!(pane.scroll, 9);
+        pane.page(-1);
+        pane.scroll_by(-3);
+        assert_eq!(pane.scroll, 0);
+    }
+
+    #[test]
+    fn short_content_does_not_scroll() {
+        let mut pane = pane(5, 10);
+        pane.scroll_by(3);
+        pane.page(1);
+        pane.bottom();
+        assert_eq!(pane.scroll, 0);
+    }
+
+    #[test]
+    fn updates_keep_the_scroll_in_range() {
+        let mut pane = pane(30, 10);
+        pane.bottom();
+        pane.update("https://github.com/org/app/issues/1", 15, 10);
+        assert_eq!(pane.scroll, 5);
+        pane.update("https://github.com/org/app/issues/2", 30, 10);
+        assert_eq!(pane.scroll, 0);
+    }
+}
@@ -0,0 +1,16 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLineKind {
+    Hunk,
+    Added,
+    Removed,
+    Context,
+    Meta,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub content: String,
+    pub old_line: Option<i32>,
+    pub new_line: Option<i32>,
+}
@@ -0,0 +1,95 @@
+use std::collections::HashSet;
+use tui::widgets::ListState;
+
+use super::{ApiResponseItem, DiffLine, DiffLineKind, DraftReviewComment, PrFile, PullRequest, ReviewComment};
+use crate::utils::parse_patch;
+
+// Files with more changed lines than this are collapsed until explicitly expanded
+pub const LARGE_DIFF_CHANGES: i32 = 400;
+
+// State of the diff pane opened on a pull request
+pub struct DiffView {
+    pub item: ApiResponseItem,
+    pub pull_request: PullRequest,
+    pub files: Vec<PrFile>,
+    pub file_list_state: ListState,
+    pub expanded_files: HashSet<String>,
+    pub focus_patch: bool,
+    pub cursor: usize,
+    pub scroll: u16,
+    pub review_comments: Vec<ReviewComment>,
+    pub pending_comments: Vec<DraftReviewComment>,
+}
+
+impl DiffView {
+    pub fn new(item: ApiResponseItem, pull_request: PullRequest, files: Vec<PrFile>, review_comments: Vec<ReviewComment>) -> DiffView {
+        let mut file_list_state = ListState::default();
+        file_list_state.select(Some(0));
+        DiffView {
+            item,
+            pull_request,
+            files,
+            file_list_state,
+            expanded_files: HashSet::new(),
+            focus_patch: false,
+            cursor: 0,
+            scroll: 0,
+            review_comments,
+            pending_comments: vec![],
+        }
+    }
+
+    pub fn selected_file(&self) -> Option<&PrFile> {
+        self.file_list_state.selected().and_then(|i| self.files.get(i))
+    }
+
+    pub fn is_collapsed(&self, file: &PrFile) -> bool {
+        file.changes > LARGE_DIFF_CHANGES && !self.expanded_files.contains(&file.filename)
+    }
+
+    // Parsed lines of the selected file, empty when the file is collapsed or has no patch
+    pub fn selected_lines(&self) -> Vec<DiffLine> {
+        match self.selected_file() {
+            Some(file) if !self.is_collapsed(file) => {
+                file.patch.as_deref().map(parse_patch).unwrap_or_default()
+            }
+            _ => vec![],
+        }
+    }
+
+    pub fn toggle_expanded(&mut self) {
+        if let Some(filename) = self.selected_file().map(|f| f.filename.clone()) {
+            if !self.expanded_files.remove(&filename) {
+                self.expanded_files.insert(filename);
+            }
+            self.cursor = 0;
+            self.scroll = 0;
+        }
+    }
+
+    pub fn move_cursor(&mut self, delta: isize) {
+        let len = self.selected_lines().len();
+        if len == 0 {
+            return;
+        }
+        self.cursor = (self.cursor as isize + delta).max(0).min(len as isize - 1) as usize;
+    }
+
+    pub fn reset_cursor(&mut self) {
+        self.cursor = 0;
+        self.scroll = 0;
+    }
+
+    // Path, line and side of the line under the cursor, as expected by the review comments API
+    pub fn comment_anchor(&self) -> Option<(String, i32, String)> {
+        let file = self.selected_file()?;
+        let line = self.selected_lines().into_iter().nth(self.cursor)?;
+        match line.kind {
+            DiffLineKind::Removed => Some((file.filename.clone(), line.old_line?, "LEFT".to_string())),
+            DiffLineKind::Added | DiffLineKind::Context => {
+                Some((file.filename.clone(), line.new_line?, "RIGHT".to_string()))
+            }
+            _ => None,
+        }
+    }
+}
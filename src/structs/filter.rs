@@ -0,0 +1,149 @@
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+
+use super::{ApiResponseItem, StaleLevel, Staleness};
+
+// One condition of the filter stack, items have to match all of them to be listed
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    Org(String),
+    Repo(String),
+    // true for pull requests, false for issues
+    Kind(bool),
+    Label(String),
+    Author(String),
+    // Created more (true) or less (false) than this many days ago
+    Age { older: bool, days: i64 },
+    // Past the staleness threshold of the config, or breaking one of its SLA rules
+    Stale,
+}
+
+impl Filter {
+    // Parse one qualifier typed in the filter input, e.g. "repo:x", "is:pr" or "age:>30"
+    pub fn parse(token: &str) -> Result<Filter, String> {
+        let (key, value) = token
+            .split_once(':')
+            .filter(|(_, value)| !value.is_empty())
+            .ok_or_else(|| format!("Unknown filter {}, expected key:value", token))?;
+        match key {
+            "org" => Ok(Filter::Org(value.to_string())),
+            "repo" => Ok(Filter::Repo(value.to_string())),
+            "is" | "type" => match value {
+                "pr" => Ok(Filter::Kind(true)),
+                "issue" => Ok(Filter::Kind(false)),
+                "stale" => Ok(Filter::Stale),
+                _ => Err(format!("Unknown kind {}, expected pr, issue or stale", value)),
+            },
+            "label" => Ok(Filter::Label(value.to_string())),
+            "author" => Ok(Filter::Author(value.to_string())),
+            "age" => {
+                let invalid = || format!("Invalid age {}, expected age:>N for older or age:<N for newer than N days", value);
+                let (older, days) = match (value.strip_prefix('>'), value.strip_prefix('<')) {
+                    (Some(days), _) => (true, days),
+                    (_, Some(days)) => (false, days),
+                    // A bare number could mean older or newer
+                    _ => return Err(invalid()),
+                };
+                days.trim_end_matches('d')
+                    .parse()
+                    .map(|days| Filter::Age { older, days })
+                    .map_err(|_| invalid())
+            }
+            _ => Err(format!("Unknown filter {}, use org, repo, is, label, author or age", key)),
+        }
+    }
+
+    // Text of the chip shown in the list title
+    pub fn label(&self) -> String {
+        match self {
+            Filter::Org(org) => format!("org:{}", org),
+            Filter::Repo(repo) => format!("repo:{}", repo),
+            Filter::Kind(true) => "is:pr".to_string(),
+            Filter::Kind(false) => "is:issue".to_string(),
+            Filter::Label(label) => format!("label:{}", label),
+            Filter::Author(author) => format!("author:{}", author),
+            Filter::Age { older, days } => format!("age:{}{}d", if *older { ">" } else { "<" }, days),
+            Filter::Stale => "is:stale".to_string(),
+        }
+    }
+
+    pub fn matches(&self, item: &ApiResponseItem, staleness: &Staleness) -> bool {
+        match self {
+            Filter::Org(org) => item.organization.as_deref() == Some(org.as_str()),
+            // "org/repo" as in GitHub queries, or the bare name picked from the repository list
+            Filter::Repo(repo) => match (item.organization.as_deref(), item.repository.as_deref()) {
+                (Some(org), Some(name)) if repo.contains('/') => *repo == format!("{}/{}", org, name),
+                (_, name) => name == Some(repo.as_str()),
+            },
+            Filter::Kind(is_pr) => item.is_pr == *is_pr,
+            Filter::Label(label) => item.labels.iter().any(|l| l.name.eq_ignore_ascii_case(label)),
+            Filter::Author(author) => item.user
+                .as_ref()
+                .map(|user| user.login.eq_ignore_ascii_case(author))
+                .unwrap_or(false),
+            Filter::Age { older, days } => match item.created_at.parse::<DateTime<Utc>>() {
+                Ok(created_at) => (Utc::now().signed_duration_since(created_at) > ChronoDuration::days(*days)) == *older,
+                Err(_) => false,
+            },
+            Filter::Stale => staleness.level(item) == StaleLevel::Stale,
+        }
+    }
+
+    // Filters of the same kind replace each other, except labels which can be combined
+    pub fn replaces(&self, other: &Filter) -> bool {
+        match (self, other) {
+            (Filter::Label(_), _) => false,
+            (Filter::Age { older, .. }, Filter::Age { older: other_older, .. }) => older == other_older,
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_each_qualifier() {
+        assert_eq!(Filter::parse("org:rust-lang"), Ok(Filter::Org("rust-lang".to_string())));
+        assert_eq!(Filter::parse("repo:rust-lang/cargo"), Ok(Filter::Repo("rust-lang/cargo".to_string())));
+        assert_eq!(Filter::parse("is:pr"), Ok(Filter::Kind(true)));
+        assert_eq!(Filter::parse("type:issue"), Ok(Filter::Kind(false)));
+        assert_eq!(Filter::parse("is:stale"), Ok(Filter::Stale));
+        assert_eq!(Filter::parse("label:bug"), Ok(Filter::Label("bug".to_string())));
+        assert_eq!(Filter::parse("author:someone"), Ok(Filter::Author("someone".to_string())));
+    }
+
+    #[test]
+    fn age_needs_a_direction() {
+        assert_eq!(Filter::parse("age:>30"), Ok(Filter::Age { older: true, days: 30 }));
+        assert_eq!(Filter::parse("age:<7d"), Ok(Filter::Age { older: false, days: 7 }));
+        assert!(Filter::parse("age:30").is_err());
+        assert!(Filter::parse("age:>soon").is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_or_empty_qualifiers() {
+        assert!(Filter::parse("bug").is_err());
+        assert!(Filter::parse("label:").is_err());
+        assert!(Filter::parse("is:draft").is_err());
+        assert!(Filter::parse("milestone:v1").is_err());
+    }
+
+    #[test]
+    fn labels_parse_back_to_the_same_filter() {
+        for token in ["org:x", "repo:x/y", "is:pr", "is:issue", "is:stale", "label:bug", "author:a", "age:>30d", "age:<2d"] {
+            assert_eq!(Filter::parse(token).map(|filter| filter.label()), Ok(token.to_string()));
+        }
+    }
+
+    #[test]
+    fn repo_matches_the_full_or_the_bare_name() {
+        let item = ApiResponseItem::sample(1, "Item");
+        let staleness = Staleness::default();
+        assert!(Filter::parse("repo:org/app").unwrap().matches(&item, &staleness));
+        assert!(Filter::parse("repo:app").unwrap().matches(&item, &staleness));
+        assert!(!Filter::parse("repo:other/app").unwrap().matches(&item, &staleness));
+        assert!(!Filter::parse("repo:org/application").unwrap().matches(&item, &staleness));
+        assert!(!Filter::parse("repo:ap").unwrap().matches(&item, &staleness));
+    }
+}
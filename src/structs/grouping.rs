@@ -0,0 +1,36 @@
+use super::ApiResponseItem;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Grouping {
+    None,
+    Repository,
+    Organization,
+}
+
+impl Grouping {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Grouping::None => "none",
+            Grouping::Repository => "repository",
+            Grouping::Organization => "organization",
+        }
+    }
+
+    pub fn next(&self) -> Grouping {
+        match self {
+            Grouping::None => Grouping::Repository,
+            Grouping::Repository => Grouping::Organization,
+            Grouping::Organization => Grouping::None,
+        }
+    }
+
+    // Name of the group the item is listed under
+    pub fn key(&self, item: &ApiResponseItem) -> String {
+        let organization = item.organization.as_deref().unwrap_or("N/A");
+        match self {
+            Grouping::None => String::new(),
+            Grouping::Repository => format!("{}/{}", organization, item.repository.as_deref().unwrap_or("N/A")),
+            Grouping::Organization => organization.to_string(),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use super::{Action, KeyContext, Keymap, Popup};
+
+// Keys of the screen or popup the help was opened on, generated from the keymap
+pub struct HelpView {
+    pub title: String,
+    // Keys and what they do, one line each
+    pub entries: Vec<(String, String)>,
+    pub scroll: u16,
+}
+
+impl HelpView {
+    pub fn new(title: &str, entries: Vec<(String, String)>) -> HelpView {
+        HelpView { title: title.to_string(), entries, scroll: 0 }
+    }
+
+    // Keys bound on the screen, described the way its first context uses them.
+    // Actions the context ignores are left out, as are the hidden ones, which only work on some screens of the context
+    pub fn for_screen(keymap: &Keymap, contexts: &[KeyContext], hidden: &[Action]) -> HelpView {
+        let entries = keymap
+            .help_entries(contexts)
+            .into_iter()
+            .filter(|(action, _)| !hidden.contains(action))
+            .filter_map(|(action, keys)| action.description_in(contexts[0]).map(|description| (keys, description.to_string())))
+            .collect();
+        HelpView::new(contexts[0].screen_name(), entries)
+    }
+
+    // Popups only use global keys, the screen below does not get them while the popup is open
+    pub fn for_popup(keymap: &Keymap, popup: Popup) -> HelpView {
+        let fixed = popup.fixed_keys().into_iter().map(|(keys, description)| (keys.to_string(), description.to_string()));
+        let bound = popup
+            .actions()
+            .into_iter()
+            .map(|(action, description)| (keymap.keys_for(&[KeyContext::Global], action), description.to_string()))
+            .filter(|(keys, _)| !keys.is_empty());
+        HelpView::new(popup.title(), fixed.chain(bound).collect())
+    }
+
+    pub fn scroll_by(&mut self, delta: i32) {
+        let max = self.entries.len().saturating_sub(1) as i32;
+        self.scroll = (self.scroll as i32 + delta).clamp(0, max) as u16;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn description<'a>(view: &'a HelpView, keys: &str) -> Option<&'a str> {
+        view.entries.iter().find(|(k, _)| k == keys).map(|(_, description)| description.as_str())
+    }
+
+    #[test]
+    fn screens_describe_the_keys_of_their_context() {
+        let keymap = Keymap::from_config(&Default::default()).unwrap();
+        let diff = HelpView::for_screen(&keymap, &[KeyContext::Diff, KeyContext::Global], &[]);
+        assert_eq!(diff.title, "Diff");
+        assert_eq!(description(&diff, "esc"), Some("Close the diff"));
+        assert_eq!(description(&diff, "e"), Some(Action::ExpandFile.description()));
+
+        let list = HelpView::for_screen(&keymap, &[KeyContext::List, KeyContext::Global], &[Action::SaveSearch]);
+        assert_eq!(description(&list, "esc"), Some(Action::Cancel.description()));
+        assert_eq!(description(&list, "S"), None);
+    }
+
+    #[test]
+    fn screens_leave_out_the_keys_they_ignore() {
+        let keymap = Keymap::from_config(&Default::default()).unwrap();
+        let inbox = HelpView::for_screen(&keymap, &[KeyContext::Inbox, KeyContext::Global], &[]);
+        assert_eq!(inbox.title, "Inbox");
+        assert_eq!(description(&inbox, "down"), Some("Next notification"));
+        assert_eq!(description(&inbox, "r"), Some(Action::MarkRead.description()));
+        for keys in ["left", "right", "pageup", "pagedown", "home", "end", "esc"] {
+            assert_eq!(description(&inbox, keys), None, "{}", keys);
+        }
+        assert!(description(&inbox, "ctrl+r").is_some());
+
+        let home = HelpView::for_screen(&keymap, &[KeyContext::Global], &[]);
+        assert_eq!(description(&home, "down"), None);
+        assert_eq!(description(&home, "?"), Some(Action::Help.description()));
+    }
+
+    #[test]
+    fn popups_list_their_fixed_keys_first() {
+        let keymap = Keymap::from_config(&Default::default()).unwrap();
+        let actions = HelpView::for_popup(&keymap, Popup::Actions { close_issue: false });
+        let keys: Vec<&str> = actions.entries.iter().map(|(keys, _)| keys.as_str()).collect();
+        assert_eq!(keys, vec!["2", "3", "4", "?", "esc"]);
+        let checks = HelpView::for_popup(&keymap, Popup::Checks);
+        assert_eq!(description(&checks, "enter"), Some("Open the log of the check"));
+    }
+}
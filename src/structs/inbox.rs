@@ -0,0 +1,128 @@
+use std::time::{Duration, Instant};
+use tui::widgets::ListState;
+
+use super::Notification;
+
+// Poll interval used until GitHub sends an X-Poll-Interval header
+pub const DEFAULT_POLL_INTERVAL: u64 = 60;
+
+// Notification threads of the Inbox tab, kept grouped by repository
+pub struct Inbox {
+    pub notifications: Vec<Notification>,
+    pub state: ListState,
+    pub error: Option<String>,
+    pub last_modified: Option<String>,
+    pub poll_interval: u64,
+    pub last_poll: Instant,
+}
+
+impl Default for Inbox {
+    fn default() -> Inbox {
+        let mut state = ListState::default();
+        state.select(Some(0));
+        Inbox {
+            notifications: vec![],
+            state,
+            error: None,
+            last_modified: None,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            last_poll: Instant::now(),
+        }
+    }
+}
+
+impl Inbox {
+    // Whether GitHub allows polling the notifications again
+    pub fn poll_due(&self) -> bool {
+        self.last_poll.elapsed() >= Duration::from_secs(self.poll_interval)
+    }
+
+    pub fn set_notifications(&mut self, mut notifications: Vec<Notification>) {
+        // Most recent first inside each repository, repositories ordered by name
+        notifications.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        notifications.sort_by(|a, b| a.repository.full_name.cmp(&b.repository.full_name));
+        let selected_id = self.selected().map(|n| n.id.clone());
+        self.notifications = notifications;
+        let index = selected_id
+            .and_then(|id| self.notifications.iter().position(|n| n.id == id))
+            .unwrap_or(0);
+        self.state.select(Some(index));
+    }
+
+    pub fn selected(&self) -> Option<&Notification> {
+        self.state.selected().and_then(|i| self.notifications.get(i))
+    }
+
+    pub fn unread_count(&self) -> usize {
+        self.notifications.iter().filter(|n| n.unread).count()
+    }
+
+    pub fn mark_read(&mut self, id: &str) {
+        for notification in self.notifications.iter_mut().filter(|n| n.id == id) {
+            notification.unread = false;
+        }
+    }
+
+    pub fn remove(&mut self, id: &str) {
+        self.notifications.retain(|n| n.id != id);
+        let last = self.notifications.len().saturating_sub(1);
+        if self.state.selected().map(|i| i > last).unwrap_or(true) {
+            self.state.select(Some(last));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inbox(ids: &[&str]) -> Inbox {
+        let mut inbox = Inbox::default();
+        inbox.set_notifications(ids.iter().map(|id| Notification::sample(id, "org/app", "2024-01-01T00:00:00Z")).collect());
+        inbox
+    }
+
+    fn ids(inbox: &Inbox) -> Vec<&str> {
+        inbox.notifications.iter().map(|n| n.id.as_str()).collect()
+    }
+
+    #[test]
+    fn notifications_are_grouped_by_repository_newest_first() {
+        let mut inbox = Inbox::default();
+        inbox.set_notifications(vec![
+            Notification::sample("1", "org/web", "2024-01-03T00:00:00Z"),
+            Notification::sample("2", "org/app", "2024-01-01T00:00:00Z"),
+            Notification::sample("3", "org/app", "2024-01-02T00:00:00Z"),
+        ]);
+        assert_eq!(ids(&inbox), vec!["3", "2", "1"]);
+    }
+
+    #[test]
+    fn refreshing_keeps_the_selected_notification() {
+        let mut inbox = inbox(&["1", "2", "3"]);
+        inbox.state.select(Some(2));
+        inbox.set_notifications(vec![
+            Notification::sample("3", "org/app", "2024-01-01T00:00:00Z"),
+            Notification::sample("4", "org/api", "2024-01-01T00:00:00Z"),
+        ]);
+        assert_eq!(inbox.selected().map(|n| n.id.as_str()), Some("3"));
+
+        inbox.set_notifications(vec![Notification::sample("5", "org/app", "2024-01-01T00:00:00Z")]);
+        assert_eq!(inbox.state.selected(), Some(0));
+    }
+
+    #[test]
+    fn removing_keeps_the_selection_in_the_list() {
+        let mut inbox = inbox(&["1", "2", "3"]);
+        inbox.state.select(Some(1));
+        inbox.remove("1");
+        assert_eq!(inbox.state.selected(), Some(1));
+        assert_eq!(inbox.selected().map(|n| n.id.as_str()), Some("3"));
+
+        inbox.remove("3");
+        assert_eq!(inbox.selected().map(|n| n.id.as_str()), Some("2"));
+        inbox.remove("2");
+        assert_eq!(inbox.state.selected(), Some(0));
+        assert!(inbox.selected().is_none());
+    }
+}
@@ -1,8 +1,32 @@
-use super::User;
+use super::{Reactions, User};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct IssueComments {
+    #[serde(default)]
+    pub id: u64,
     pub body: String,
     pub user: User,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub updated_at: String,
+    // OWNER, MEMBER, COLLABORATOR, CONTRIBUTOR, FIRST_TIME_CONTRIBUTOR, NONE...
+    #[serde(default)]
+    pub author_association: String,
+    #[serde(default)]
+    pub reactions: Option<Reactions>,
+    #[serde(default)]
+    pub html_url: String,
+}
+
+impl IssueComments {
+    // Count a reaction the user just left, the API only returns the reaction itself
+    pub fn add_reaction(&mut self, content: &str) {
+        self.reactions.get_or_insert_with(Reactions::default).add(content);
+    }
+
+    pub fn edited(&self) -> bool {
+        !self.updated_at.is_empty() && self.updated_at != self.created_at
+    }
 }
@@ -0,0 +1,346 @@
+use fuzzy_matcher::skim::SkimMatcherV2;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use tui::widgets::ListState;
+
+use super::{ApiResponseItem, Filter, Grouping, Priorities, SortMode, Staleness};
+use crate::utils::fuzzy_match;
+
+// Index of an item and the chars of its title matched by the fuzzy filter
+type VisibleItem = (usize, Vec<usize>);
+
+// One line of the list: a group header, or the position of an item in `visible`
+pub enum TabRow {
+    Header { name: String, count: usize, collapsed: bool },
+    Item(usize),
+}
+
+// Items of a search shown in a list tab, with the selection of that tab
+pub struct IssueTab {
+    pub title: String,
+    pub items: Vec<ApiResponseItem>,
+    pub total_count: i32,
+    pub state: ListState,
+    // Why the search could not be run, shown instead of the list
+    pub error: Option<String>,
+    // Filter stack shared by all the tabs, the items themselves are never dropped by it
+    pub filters: Vec<Filter>,
+    // Thresholds of the config, for the colors, the age column and the stale filter
+    pub staleness: Staleness,
+    // Priorities set by the user, for the priority sort and the list
+    pub priorities: Priorities,
+    // Order of the list when no fuzzy filter ranks it
+    pub sort: SortMode,
+    pub sort_descending: bool,
+    // Local fuzzy filter typed by the user
+    pub fuzzy: String,
+    // What the list shows, in order: index in items and the matched chars of the title
+    pub visible: Vec<VisibleItem>,
+    pub grouping: Grouping,
+    // Names of the groups folded under their header
+    pub collapsed: HashSet<String>,
+    // Lines of the list, the selection of the tab indexes them
+    pub rows: Vec<TabRow>,
+}
+
+impl IssueTab {
+    pub fn new(title: &str, items: Vec<ApiResponseItem>, total_count: i32) -> IssueTab {
+        let mut state = ListState::default();
+        state.select(Some(0));
+        let mut tab = IssueTab {
+            title: title.to_string(),
+            items,
+            total_count,
+            state,
+            error: None,
+            filters: vec![],
+            staleness: Staleness::default(),
+            priorities: Priorities::default(),
+            sort: SortMode::Updated,
+            sort_descending: true,
+            fuzzy: String::new(),
+            visible: vec![],
+            grouping: Grouping::None,
+            collapsed: HashSet::new(),
+            rows: vec![],
+        };
+        tab.refresh_view();
+        tab
+    }
+
+    // Rebuild the visible list from the items matching the filters, the best fuzzy matches first
+    pub fn refresh_view(&mut self) {
+        let filtered = self.items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| self.filters.iter().all(|filter| filter.matches(item, &self.staleness)));
+        if self.fuzzy.trim().is_empty() {
+            let mut visible: Vec<usize> = filtered.map(|(i, _)| i).collect();
+            visible.sort_by(|a, b| {
+                let order = self.sort.compare(&self.items[*a], &self.items[*b], &self.staleness, &self.priorities);
+                if self.sort_descending { order.reverse() } else { order }
+            });
+            self.visible = visible.into_iter().map(|i| (i, vec![])).collect();
+        } else {
+            let matcher = SkimMatcherV2::default();
+            let mut matches: Vec<(i64, usize, Vec<usize>)> = filtered
+                .filter_map(|(i, item)| {
+                    fuzzy_match(&matcher, item, self.fuzzy.trim()).map(|(score, indices)| (score, i, indices))
+                })
+                .collect();
+            matches.sort_by_key(|(score, _, _)| Reverse(*score));
+            self.visible = matches.into_iter().map(|(_, i, indices)| (i, indices)).collect();
+        }
+        self.build_rows();
+        if !self.state.selected().map(|row| self.selectable(row)).unwrap_or(false) {
+            let first = (0..self.rows.len()).find(|row| self.selectable(*row));
+            self.state.select(Some(first.unwrap_or(0)));
+        }
+    }
+
+    // Regroup the visible items under their headers, groups come in the order of their first item
+    fn build_rows(&mut self) {
+        if self.grouping == Grouping::None {
+            self.rows = (0..self.visible.len()).map(TabRow::Item).collect();
+            return;
+        }
+        let mut groups: Vec<(String, Vec<VisibleItem>)> = vec![];
+        for entry in self.visible.drain(..) {
+            let key = self.grouping.key(&self.items[entry.0]);
+            match groups.iter_mut().find(|(name, _)| *name == key) {
+                Some((_, entries)) => entries.push(entry),
+                None => groups.push((key, vec![entry])),
+            }
+        }
+        self.rows = vec![];
+        for (name, entries) in groups {
+            let collapsed = self.collapsed.contains(&name);
+            self.rows.push(TabRow::Header { name, count: entries.len(), collapsed });
+            for entry in entries {
+                if !collapsed {
+                    self.rows.push(TabRow::Item(self.visible.len()));
+                }
+                self.visible.push(entry);
+            }
+        }
+    }
+
+    // Headers are skipped, except collapsed ones which stand for their whole group
+    fn selectable(&self, row: usize) -> bool {
+        matches!(self.rows.get(row), Some(TabRow::Item(_)) | Some(TabRow::Header { collapsed: true, .. }))
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        let mut selected = self.state.selected().unwrap_or(0);
+        for _ in 0..delta.unsigned_abs() {
+            let next = if delta > 0 {
+                (selected + 1..self.rows.len()).find(|row| self.selectable(*row))
+            } else {
+                (0..selected).rev().find(|row| self.selectable(*row))
+            };
+            match next {
+                Some(row) => selected = row,
+                None => break,
+            }
+        }
+        self.state.select(Some(selected));
+    }
+
+    pub fn set_filters(&mut self, filters: &[Filter], staleness: &Staleness) {
+        self.filters = filters.to_vec();
+        self.staleness = staleness.clone();
+        self.state.select(Some(0));
+        self.refresh_view();
+    }
+
+    // Re-sort with new priorities, keeping the selected item selected
+    pub fn set_priorities(&mut self, priorities: &Priorities) {
+        let selected = self.selected_item().map(|item| item.url.clone());
+        self.priorities = priorities.clone();
+        self.refresh_view();
+        if let Some(url) = selected {
+            self.select_url(&url);
+        }
+    }
+
+    pub fn set_sort(&mut self, sort: SortMode, descending: bool) {
+        self.sort = sort;
+        self.sort_descending = descending;
+        self.state.select(Some(0));
+        self.refresh_view();
+    }
+
+    pub fn set_grouping(&mut self, grouping: Grouping) {
+        self.grouping = grouping;
+        self.state.select(Some(0));
+        self.refresh_view();
+    }
+
+    // Fold or unfold the group of the selected line, keeping the selection on that group
+    pub fn toggle_group(&mut self) {
+        let name = match self.state.selected().and_then(|row| self.rows.get(row)) {
+            Some(TabRow::Header { name, .. }) => name.clone(),
+            Some(TabRow::Item(position)) => self.grouping.key(&self.items[self.visible[*position].0]),
+            None => return,
+        };
+        if !self.collapsed.remove(&name) {
+            self.collapsed.insert(name.clone());
+        }
+        self.refresh_view();
+        let header = self.rows.iter().position(|row| matches!(row, TabRow::Header { name: n, .. } if *n == name));
+        if let Some(header) = header {
+            let collapsed = self.collapsed.contains(&name);
+            self.state.select(Some(if collapsed { header } else { header + 1 }));
+        }
+    }
+
+    // Keep the order and grouping picked in the tab this one replaces, e.g. after a refresh
+    pub fn restore_view(&mut self, previous: &IssueTab) {
+        self.sort = previous.sort;
+        self.priorities = previous.priorities.clone();
+        self.sort_descending = previous.sort_descending;
+        self.grouping = previous.grouping;
+        self.collapsed = previous.collapsed.clone();
+        self.refresh_view();
+    }
+
+    // Select the item with the given url, unfolding its group. False when the tab does not show it
+    pub fn select_url(&mut self, url: &str) -> bool {
+        let Some(index) = self.items.iter().position(|item| item.url == url) else {
+            return false;
+        };
+        if self.grouping != Grouping::None && self.collapsed.remove(&self.grouping.key(&self.items[index])) {
+            self.refresh_view();
+        }
+        let row = self.rows
+            .iter()
+            .position(|row| matches!(row, TabRow::Item(position) if self.visible[*position].0 == index));
+        match row {
+            Some(row) => {
+                self.state.select(Some(row));
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn set_fuzzy(&mut self, pattern: &str) {
+        self.fuzzy = pattern.to_string();
+        self.state.select(Some(0));
+        self.refresh_view();
+    }
+
+    pub fn selected_item(&self) -> Option<&ApiResponseItem> {
+        match self.state.selected().and_then(|row| self.rows.get(row)) {
+            Some(TabRow::Item(position)) => self.visible.get(*position).map(|(i, _)| &self.items[*i]),
+            _ => None,
+        }
+    }
+
+    // Drop the item with the given url, e.g. once it has been closed or merged
+    pub fn remove_item(&mut self, url: &str) {
+        let before = self.items.len();
+        self.items.retain(|item| item.url != url);
+        let removed = (before - self.items.len()) as i32;
+        if removed > 0 {
+            self.total_count -= removed;
+            self.state.select(Some(0));
+            self.refresh_view();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Issues 1 and 3 are in org/a, 2 and 4 in org/b, listed by number
+    fn grouped_tab() -> IssueTab {
+        let items = (1..=4)
+            .map(|number| {
+                let mut item = ApiResponseItem::sample(number, "Item");
+                item.url = format!("https://github.com/{}", number);
+                item.repository = Some(if number % 2 == 1 { "a" } else { "b" }.to_string());
+                item
+            })
+            .collect();
+        let mut tab = IssueTab::new("Tab", items, 4);
+        tab.set_sort(SortMode::Number, false);
+        tab.set_grouping(Grouping::Repository);
+        tab
+    }
+
+    fn rows(tab: &IssueTab) -> Vec<String> {
+        tab.rows
+            .iter()
+            .map(|row| match row {
+                TabRow::Header { name, count, collapsed } => format!("{}{} ({})", if *collapsed { "+" } else { "" }, name, count),
+                TabRow::Item(position) => format!("#{}", tab.items[tab.visible[*position].0].number),
+            })
+            .collect()
+    }
+
+    fn selected_number(tab: &IssueTab) -> Option<i32> {
+        tab.selected_item().map(|item| item.number)
+    }
+
+    #[test]
+    fn groups_follow_their_first_item() {
+        let tab = grouped_tab();
+        assert_eq!(rows(&tab), vec!["org/a (2)", "#1", "#3", "org/b (2)", "#2", "#4"]);
+        // The header is skipped by the initial selection
+        assert_eq!(selected_number(&tab), Some(1));
+    }
+
+    #[test]
+    fn moving_skips_expanded_headers() {
+        let mut tab = grouped_tab();
+        tab.move_selection(2);
+        assert_eq!(selected_number(&tab), Some(2));
+        tab.move_selection(-1);
+        assert_eq!(selected_number(&tab), Some(3));
+        tab.move_selection(-5);
+        assert_eq!(selected_number(&tab), Some(1));
+    }
+
+    #[test]
+    fn collapsed_group_is_selected_by_its_header() {
+        let mut tab = grouped_tab();
+        tab.toggle_group();
+        assert_eq!(rows(&tab), vec!["+org/a (2)", "org/b (2)", "#2", "#4"]);
+        assert_eq!(tab.state.selected(), Some(0));
+        assert_eq!(selected_number(&tab), None);
+
+        tab.move_selection(1);
+        assert_eq!(selected_number(&tab), Some(2));
+        tab.move_selection(-1);
+        assert_eq!(tab.state.selected(), Some(0));
+
+        tab.toggle_group();
+        assert_eq!(rows(&tab), vec!["org/a (2)", "#1", "#3", "org/b (2)", "#2", "#4"]);
+        assert_eq!(selected_number(&tab), Some(1));
+    }
+
+    #[test]
+    fn selecting_an_item_unfolds_its_group() {
+        let mut tab = grouped_tab();
+        tab.move_selection(2);
+        tab.toggle_group();
+        assert_eq!(rows(&tab), vec!["org/a (2)", "#1", "#3", "+org/b (2)"]);
+        assert!(tab.select_url("https://github.com/4"));
+        assert_eq!(selected_number(&tab), Some(4));
+        assert!(!tab.select_url("https://github.com/5"));
+    }
+
+    #[test]
+    fn selection_stays_on_a_row_when_the_view_changes() {
+        let mut tab = grouped_tab();
+        tab.toggle_group();
+        tab.set_filters(&[Filter::Repo("a".to_string())], &Staleness::default());
+        assert_eq!(rows(&tab), vec!["+org/a (2)"]);
+        assert_eq!(tab.state.selected(), Some(0));
+        tab.set_filters(&[Filter::Repo("b".to_string())], &Staleness::default());
+        assert_eq!(rows(&tab), vec!["org/b (2)", "#2", "#4"]);
+        assert_eq!(selected_number(&tab), Some(2));
+    }
+}
@@ -0,0 +1,169 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::fmt;
+
+// One key press as written in the keymap, e.g. "ctrl+r", "O", "pagedown"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Key {
+    // Shift is carried by the case of letters, terminals do not all report it for them
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Key {
+        let mut modifiers = modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        if matches!(code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Key { code, modifiers }
+    }
+
+    pub fn from_event(event: KeyEvent) -> Key {
+        Key::new(event.code, event.modifiers)
+    }
+
+    pub fn parse(value: &str) -> Result<Key, String> {
+        let (modifier_names, name) = match value.rsplit_once('+') {
+            // "+" and "ctrl++" bind the plus key itself
+            Some((rest, "")) => (rest.strip_suffix('+').unwrap_or(rest), "+"),
+            Some((rest, name)) => (rest, name),
+            None => ("", value),
+        };
+        let mut modifiers = KeyModifiers::NONE;
+        for modifier in modifier_names.split('+').filter(|m| !m.is_empty()) {
+            modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("Unknown modifier \"{}\" in \"{}\"", modifier, value)),
+            };
+        }
+        let code = match name.to_lowercase().as_str() {
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "space" => KeyCode::Char(' '),
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "delete" | "del" => KeyCode::Delete,
+            "insert" => KeyCode::Insert,
+            lower => match (lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()), name.chars().count()) {
+                (Some(n), _) if (1..=12).contains(&n) => KeyCode::F(n),
+                (_, 1) => {
+                    let c = name.chars().next().unwrap_or_default();
+                    KeyCode::Char(if modifiers.contains(KeyModifiers::SHIFT) { c.to_ascii_uppercase() } else { c })
+                }
+                _ => return Err(format!("Unknown key \"{}\" in \"{}\"", name, value)),
+            },
+        };
+        Ok(Key::new(code, modifiers))
+    }
+
+    // Keys separated by spaces are pressed one after the other, e.g. "g g"
+    pub fn parse_sequence(value: &str) -> Result<Vec<Key>, String> {
+        let keys = value.split_whitespace().map(Key::parse).collect::<Result<Vec<Key>, String>>()?;
+        if keys.is_empty() {
+            return Err("Empty key binding".to_string());
+        }
+        Ok(keys)
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            KeyCode::Enter => write!(f, "enter"),
+            KeyCode::Esc => write!(f, "esc"),
+            KeyCode::Tab => write!(f, "tab"),
+            KeyCode::BackTab => write!(f, "shift+tab"),
+            KeyCode::Backspace => write!(f, "backspace"),
+            KeyCode::Up => write!(f, "up"),
+            KeyCode::Down => write!(f, "down"),
+            KeyCode::Left => write!(f, "left"),
+            KeyCode::Right => write!(f, "right"),
+            KeyCode::PageUp => write!(f, "pageup"),
+            KeyCode::PageDown => write!(f, "pagedown"),
+            KeyCode::Home => write!(f, "home"),
+            KeyCode::End => write!(f, "end"),
+            KeyCode::Delete => write!(f, "delete"),
+            KeyCode::Insert => write!(f, "insert"),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> Key {
+        Key::new(code, modifiers)
+    }
+
+    #[test]
+    fn parses_modifiers_and_named_keys() {
+        assert_eq!(Key::parse("ctrl+r"), Ok(key(KeyCode::Char('r'), KeyModifiers::CONTROL)));
+        assert_eq!(Key::parse("Ctrl+Alt+x"), Ok(key(KeyCode::Char('x'), KeyModifiers::CONTROL | KeyModifiers::ALT)));
+        assert_eq!(Key::parse("PageDown"), Ok(key(KeyCode::PageDown, KeyModifiers::NONE)));
+        assert_eq!(Key::parse("space"), Ok(key(KeyCode::Char(' '), KeyModifiers::NONE)));
+        assert_eq!(Key::parse("f5"), Ok(key(KeyCode::F(5), KeyModifiers::NONE)));
+        assert_eq!(Key::parse("f"), Ok(key(KeyCode::Char('f'), KeyModifiers::NONE)));
+    }
+
+    #[test]
+    fn shift_is_carried_by_the_case_of_letters() {
+        assert_eq!(Key::parse("shift+o"), Key::parse("O"));
+        assert_eq!(Key::parse("shift+tab"), Ok(key(KeyCode::BackTab, KeyModifiers::NONE)));
+        // Terminals report SHIFT + o as "O" with or without the modifier
+        assert_eq!(key(KeyCode::Char('O'), KeyModifiers::SHIFT), key(KeyCode::Char('O'), KeyModifiers::NONE));
+        assert_eq!(key(KeyCode::Up, KeyModifiers::SHIFT).modifiers, KeyModifiers::SHIFT);
+    }
+
+    #[test]
+    fn plus_can_be_bound() {
+        assert_eq!(Key::parse("+"), Ok(key(KeyCode::Char('+'), KeyModifiers::NONE)));
+        assert_eq!(Key::parse("ctrl++"), Ok(key(KeyCode::Char('+'), KeyModifiers::CONTROL)));
+    }
+
+    #[test]
+    fn rejects_unknown_names() {
+        assert!(Key::parse("hyper+x").is_err());
+        assert!(Key::parse("f13").is_err());
+        assert!(Key::parse("enterr").is_err());
+    }
+
+    #[test]
+    fn sequences_are_split_on_spaces() {
+        assert_eq!(Key::parse_sequence("g  g").map(|keys| keys.len()), Ok(2));
+        assert!(Key::parse_sequence(" ").is_err());
+        assert!(Key::parse_sequence("g nope").is_err());
+    }
+
+    #[test]
+    fn display_is_the_config_syntax() {
+        for value in ["ctrl+r", "alt+x", "O", "space", "enter", "shift+tab", "shift+up", "f5"] {
+            assert_eq!(Key::parse(value).map(|key| key.to_string()), Ok(value.to_string()));
+        }
+    }
+}
@@ -0,0 +1,379 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+
+use super::{Action, Key};
+
+// Where a binding applies, global bindings work alongside those of the current screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyContext {
+    Global,
+    List,
+    Diff,
+    Inbox,
+}
+
+pub const KEY_CONTEXTS: [KeyContext; 4] = [KeyContext::Global, KeyContext::List, KeyContext::Diff, KeyContext::Inbox];
+
+impl KeyContext {
+    // Name of the context in the [keys] section of the config
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyContext::Global => "global",
+            KeyContext::List => "list",
+            KeyContext::Diff => "diff",
+            KeyContext::Inbox => "inbox",
+        }
+    }
+
+    // Name of the screen the context is first in, shown by the help
+    pub fn screen_name(&self) -> &'static str {
+        match self {
+            KeyContext::Global => "Home",
+            KeyContext::List => "List",
+            KeyContext::Diff => "Diff",
+            KeyContext::Inbox => "Inbox",
+        }
+    }
+}
+
+type DefaultBinding = (KeyContext, Action, &'static [&'static str]);
+
+// Keys of every action out of the box, an action can only be bound in the contexts listed here
+const DEFAULT_BINDINGS: [DefaultBinding; 52] = [
+    (KeyContext::Global, Action::Quit, &["q", "ctrl+c"]),
+    // Many terminals send CTRL + h as backspace
+    (KeyContext::Global, Action::Home, &["ctrl+h", "backspace"]),
+    (KeyContext::Global, Action::Assignments, &["ctrl+a"]),
+    (KeyContext::Global, Action::Closed, &["ctrl+x"]),
+    (KeyContext::Global, Action::ToReview, &["ctrl+t"]),
+    (KeyContext::Global, Action::Authored, &["ctrl+u"]),
+    (KeyContext::Global, Action::Mentions, &["ctrl+e"]),
+    (KeyContext::Global, Action::Inbox, &["ctrl+n"]),
+    (KeyContext::Global, Action::NextTab, &["]"]),
+    (KeyContext::Global, Action::PreviousTab, &["["]),
+    (KeyContext::Global, Action::Refresh, &["ctrl+r"]),
+    (KeyContext::Global, Action::Search, &["/"]),
+    (KeyContext::Global, Action::MoveUp, &["up"]),
+    (KeyContext::Global, Action::MoveDown, &["down"]),
+    (KeyContext::Global, Action::MoveLeft, &["left"]),
+    (KeyContext::Global, Action::MoveRight, &["right"]),
+    (KeyContext::Global, Action::PageUp, &["pageup"]),
+    (KeyContext::Global, Action::PageDown, &["pagedown"]),
+    (KeyContext::Global, Action::Top, &["home"]),
+    (KeyContext::Global, Action::Bottom, &["end"]),
+    (KeyContext::Global, Action::Confirm, &["enter"]),
+    (KeyContext::Global, Action::Cancel, &["esc"]),
+    (KeyContext::Global, Action::Help, &["?"]),
+    (KeyContext::List, Action::ToggleFocus, &["tab"]),
+    (KeyContext::List, Action::ToggleHidden, &["H"]),
+    (KeyContext::List, Action::Actions, &["n"]),
+    (KeyContext::List, Action::SaveSearch, &["S"]),
+    (KeyContext::List, Action::FuzzyFilter, &["f"]),
+    (KeyContext::List, Action::Sort, &["o"]),
+    (KeyContext::List, Action::SortOrder, &["O"]),
+    (KeyContext::List, Action::Priority, &["p"]),
+    (KeyContext::List, Action::Group, &["v"]),
+    (KeyContext::List, Action::ToggleGroup, &["space"]),
+    (KeyContext::List, Action::AddFilters, &["F"]),
+    (KeyContext::List, Action::FilterPullRequests, &["P"]),
+    (KeyContext::List, Action::FilterIssues, &["I"]),
+    (KeyContext::List, Action::RemoveFilter, &["X"]),
+    (KeyContext::List, Action::ClearFilters, &["0"]),
+    (KeyContext::List, Action::Diff, &["d"]),
+    (KeyContext::List, Action::Merge, &["m"]),
+    (KeyContext::List, Action::Checks, &["b"]),
+    (KeyContext::List, Action::React, &["e"]),
+    (KeyContext::List, Action::Timeline, &["t"]),
+    (KeyContext::Diff, Action::ExpandFile, &["e"]),
+    (KeyContext::Diff, Action::Comment, &["c"]),
+    (KeyContext::Diff, Action::AddToReview, &["a"]),
+    (KeyContext::Diff, Action::SubmitReview, &["s"]),
+    (KeyContext::Diff, Action::Merge, &["m"]),
+    (KeyContext::Diff, Action::Checks, &["b"]),
+    (KeyContext::Inbox, Action::MarkRead, &["r"]),
+    (KeyContext::Inbox, Action::MarkDone, &["x"]),
+    (KeyContext::Inbox, Action::Unsubscribe, &["u"]),
+];
+
+// Added by `preset = "vim"`
+const VIM_BINDINGS: [DefaultBinding; 4] = [
+    (KeyContext::Global, Action::MoveDown, &["j"]),
+    (KeyContext::Global, Action::MoveUp, &["k"]),
+    (KeyContext::Global, Action::Top, &["g g"]),
+    (KeyContext::Global, Action::Bottom, &["G"]),
+];
+
+// One key sequence or several for the same action
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    fn values(&self) -> Vec<&str> {
+        match self {
+            KeyList::One(value) => vec![value.as_str()],
+            KeyList::Many(values) => values.iter().map(|value| value.as_str()).collect(),
+        }
+    }
+}
+
+// The [keys] section of the config: a preset and, by context, the keys replacing those of an action
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct KeysConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub global: HashMap<String, KeyList>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub list: HashMap<String, KeyList>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub diff: HashMap<String, KeyList>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub inbox: HashMap<String, KeyList>,
+}
+
+impl KeysConfig {
+    pub fn is_empty(&self) -> bool {
+        self.preset.is_none() && KEY_CONTEXTS.iter().all(|context| self.overrides(*context).is_empty())
+    }
+
+    fn overrides(&self, context: KeyContext) -> &HashMap<String, KeyList> {
+        match context {
+            KeyContext::Global => &self.global,
+            KeyContext::List => &self.list,
+            KeyContext::Diff => &self.diff,
+            KeyContext::Inbox => &self.inbox,
+        }
+    }
+}
+
+pub struct Binding {
+    pub context: KeyContext,
+    pub keys: Vec<Key>,
+    pub action: Action,
+}
+
+// Keys of a sequence as written in the config, e.g. "g g"
+pub fn sequence_label(keys: &[Key]) -> String {
+    keys.iter().map(|key| key.to_string()).collect::<Vec<String>>().join(" ")
+}
+
+pub struct Keymap {
+    pub bindings: Vec<Binding>,
+    // Keys typed so far of a sequence that is not complete yet
+    pending: Vec<Key>,
+}
+
+impl Keymap {
+    // Defaults, then the preset and the overrides of the config. Unknown names and conflicts are errors
+    pub fn from_config(config: &KeysConfig) -> Result<Keymap, Box<dyn Error>> {
+        let mut defaults = DEFAULT_BINDINGS.to_vec();
+        match config.preset.as_deref() {
+            None | Some("default") => {}
+            Some("vim") => defaults.extend(VIM_BINDINGS),
+            Some(preset) => return Err(format!("Unknown key preset \"{}\", expected default or vim", preset).into()),
+        }
+
+        let mut bindings = vec![];
+        for (context, action, values) in defaults {
+            if config.overrides(context).contains_key(action.name()) {
+                continue;
+            }
+            for value in values {
+                bindings.push(Binding { context, keys: Key::parse_sequence(value)?, action });
+            }
+        }
+        for context in KEY_CONTEXTS {
+            for (name, values) in config.overrides(context) {
+                let action = Action::from_name(name)
+                    .ok_or_else(|| format!("Unknown action \"{}\" in keys.{}", name, context.name()))?;
+                if !DEFAULT_BINDINGS.iter().any(|(c, a, _)| *c == context && *a == action) {
+                    return Err(format!("{} cannot be bound in keys.{}", name, context.name()).into());
+                }
+                for value in values.values() {
+                    let keys = Key::parse_sequence(value).map_err(|e| format!("keys.{}.{}: {}", context.name(), name, e))?;
+                    bindings.push(Binding { context, keys, action });
+                }
+            }
+        }
+
+        let keymap = Keymap { bindings, pending: vec![] };
+        let conflicts = keymap.conflicts();
+        if !conflicts.is_empty() {
+            return Err(format!("Conflicting key bindings: {}", conflicts.join(", ")).into());
+        }
+        Ok(keymap)
+    }
+
+    // Bindings active at the same time where one sequence is the start of, or the same as, another
+    fn conflicts(&self) -> Vec<String> {
+        let mut conflicts = vec![];
+        for (index, first) in self.bindings.iter().enumerate() {
+            for second in &self.bindings[index + 1..] {
+                let overlapping = first.context == second.context
+                    || first.context == KeyContext::Global
+                    || second.context == KeyContext::Global;
+                let ambiguous = first.keys.starts_with(&second.keys) || second.keys.starts_with(&first.keys);
+                let duplicate = first.action == second.action && first.keys == second.keys;
+                if overlapping && ambiguous && !duplicate {
+                    conflicts.push(format!(
+                        "\"{}\" ({} in {}) and \"{}\" ({} in {})",
+                        sequence_label(&first.keys),
+                        first.action.name(),
+                        first.context.name(),
+                        sequence_label(&second.keys),
+                        second.action.name(),
+                        second.context.name(),
+                    ));
+                }
+            }
+        }
+        conflicts
+    }
+
+    // Keys of the action in the contexts, e.g. "q / ctrl+c"
+    pub fn keys_for(&self, contexts: &[KeyContext], action: Action) -> String {
+        self.bindings
+            .iter()
+            .filter(|binding| binding.action == action && contexts.contains(&binding.context))
+            .map(|binding| sequence_label(&binding.keys))
+            .collect::<Vec<String>>()
+            .join(" / ")
+    }
+
+    // Keys of a few actions for a title, e.g. "enter: open log - esc: close". Unbound actions are left out
+    pub fn hint(&self, contexts: &[KeyContext], entries: &[(Action, &str)]) -> String {
+        entries
+            .iter()
+            .map(|(action, label)| (self.keys_for(contexts, *action), label))
+            .filter(|(keys, _)| !keys.is_empty())
+            .map(|(keys, label)| format!("{}: {}", keys, label))
+            .collect::<Vec<String>>()
+            .join(" - ")
+    }
+
+    // Actions bound in the contexts with their keys, those of the first context first
+    pub fn help_entries(&self, contexts: &[KeyContext]) -> Vec<(Action, String)> {
+        let mut entries: Vec<(Action, String)> = vec![];
+        for context in contexts {
+            for binding in self.bindings.iter().filter(|binding| binding.context == *context) {
+                if !entries.iter().any(|(action, _)| *action == binding.action) {
+                    entries.push((binding.action, self.keys_for(&[*context], binding.action)));
+                }
+            }
+        }
+        entries
+    }
+
+    // Feed a key press: the action once a whole sequence is typed, None while it is incomplete or unbound
+    pub fn resolve(&mut self, contexts: &[KeyContext], key: Key) -> Option<Action> {
+        self.pending.push(key);
+        loop {
+            let candidates: Vec<&Binding> = self.bindings
+                .iter()
+                .filter(|binding| contexts.contains(&binding.context) && binding.keys.starts_with(&self.pending))
+                .collect();
+            let complete = candidates.iter().find(|binding| binding.keys == self.pending).map(|binding| binding.action);
+            if complete.is_some() {
+                self.pending.clear();
+                return complete;
+            }
+            if !candidates.is_empty() {
+                return None;
+            }
+            // The key breaking a sequence may start one of its own
+            if self.pending.len() > 1 {
+                self.pending = vec![key];
+            } else {
+                self.pending.clear();
+                return None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keymap(toml: &str) -> Result<Keymap, Box<dyn Error>> {
+        Keymap::from_config(&toml::from_str::<KeysConfig>(toml)?)
+    }
+
+    fn press(keymap: &mut Keymap, contexts: &[KeyContext], keys: &str) -> Vec<Option<Action>> {
+        Key::parse_sequence(keys).unwrap().into_iter().map(|key| keymap.resolve(contexts, key)).collect()
+    }
+
+    const LIST: [KeyContext; 2] = [KeyContext::List, KeyContext::Global];
+    const INBOX: [KeyContext; 2] = [KeyContext::Inbox, KeyContext::Global];
+
+    #[test]
+    fn defaults_do_not_conflict() {
+        assert!(keymap("").is_ok());
+        assert!(keymap("preset = \"vim\"").is_ok());
+    }
+
+    #[test]
+    fn conflicts_within_a_context_or_with_global() {
+        let error = keymap("[list]\nsort = \"d\"").err().unwrap().to_string();
+        assert!(error.contains("\"d\" (sort in list)"), "{}", error);
+        assert!(error.contains("(diff in list)"), "{}", error);
+
+        assert!(keymap("[global]\nquit = \"t\"").is_err());
+        // "g" alone would shadow "g g"
+        assert!(keymap("preset = \"vim\"\n[list]\nsort = \"g\"").is_err());
+        // The diff and the lists are never active together
+        assert!(keymap("[diff]\ncomment = \"t\"").is_ok());
+    }
+
+    #[test]
+    fn unknown_or_misplaced_actions_are_errors() {
+        assert!(keymap("[list]\nfly = \"y\"").is_err());
+        assert!(keymap("[inbox]\nsort = \"y\"").is_err());
+        assert!(keymap("preset = \"emacs\"").is_err());
+        assert!(keymap("[list]\nsort = \"hyper+y\"").is_err());
+    }
+
+    #[test]
+    fn overrides_replace_the_default_keys() {
+        let keymap = keymap("[global]\nquit = [\"q\", \"ctrl+q\"]\n[list]\nreact = \"r e\"").unwrap();
+        assert_eq!(keymap.keys_for(&LIST, Action::Quit), "q / ctrl+q");
+        assert_eq!(keymap.keys_for(&LIST, Action::React), "r e");
+    }
+
+    #[test]
+    fn resolves_sequences() {
+        let mut keymap = keymap("preset = \"vim\"").unwrap();
+        assert_eq!(press(&mut keymap, &LIST, "g g"), vec![None, Some(Action::Top)]);
+        assert_eq!(press(&mut keymap, &LIST, "j"), vec![Some(Action::MoveDown)]);
+        // The key breaking a sequence starts over on its own
+        assert_eq!(press(&mut keymap, &LIST, "g j"), vec![None, Some(Action::MoveDown)]);
+        assert_eq!(press(&mut keymap, &LIST, "y j"), vec![None, Some(Action::MoveDown)]);
+    }
+
+    #[test]
+    fn pending_sequence_does_not_leak_into_another_context() {
+        let mut keymap = keymap("[list]\nreact = \"r e\"").unwrap();
+        assert_eq!(press(&mut keymap, &LIST, "r"), vec![None]);
+        // "r e" is not bound in the inbox, so "e" is looked up alone there
+        assert_eq!(press(&mut keymap, &INBOX, "e"), vec![None]);
+        assert_eq!(press(&mut keymap, &LIST, "e"), vec![None]);
+
+        assert_eq!(press(&mut keymap, &LIST, "r"), vec![None]);
+        assert_eq!(press(&mut keymap, &INBOX, "r"), vec![Some(Action::MarkRead)]);
+        assert_eq!(press(&mut keymap, &LIST, "r e"), vec![None, Some(Action::React)]);
+    }
+
+    #[test]
+    fn hints_leave_unbound_actions_out() {
+        let keymap = keymap("").unwrap();
+        assert_eq!(
+            keymap.hint(&INBOX, &[(Action::Confirm, "open"), (Action::Diff, "diff"), (Action::MarkRead, "mark read")]),
+            "enter: open - r: mark read"
+        );
+    }
+}
@@ -1,6 +1,56 @@
 use serde::{Deserialize, Serialize};
+use tui::style::Color;
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Label {
   pub name: String,
+  // Hex color without the leading #, e.g. "d73a4a"
+  #[serde(default)]
+  pub color: String,
+  #[serde(default)]
+  pub description: Option<String>,
+}
+
+impl Label {
+  pub fn background(&self) -> Color {
+      let channel = |i: usize| self.color.get(i..i + 2).and_then(|hex| u8::from_str_radix(hex, 16).ok());
+      match (channel(0), channel(2), channel(4)) {
+          (Some(r), Some(g), Some(b)) => Color::Rgb(r, g, b),
+          _ => Color::DarkGray,
+      }
+  }
+
+  // Black or white, whichever reads best on the label color
+  pub fn foreground(&self) -> Color {
+      match self.background() {
+          Color::Rgb(r, g, b) if 299 * r as u32 + 587 * g as u32 + 114 * b as u32 > 128_000 => Color::Black,
+          _ => Color::White,
+      }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(color: &str) -> Label {
+        Label { name: "bug".to_string(), color: color.to_string(), description: None }
+    }
+
+    #[test]
+    fn text_contrasts_with_the_label_color() {
+        assert_eq!(label("d73a4a").background(), Color::Rgb(0xd7, 0x3a, 0x4a));
+        assert_eq!(label("ffffff").foreground(), Color::Black);
+        assert_eq!(label("fbca04").foreground(), Color::Black);
+        assert_eq!(label("000000").foreground(), Color::White);
+        assert_eq!(label("0e4a86").foreground(), Color::White);
+    }
+
+    #[test]
+    fn malformed_colors_fall_back_to_gray() {
+        for color in ["", "fff", "zzzzzz", "#d73a4a"] {
+            assert_eq!(label(color).background(), Color::DarkGray, "{}", color);
+            assert_eq!(label(color).foreground(), Color::White, "{}", color);
+        }
+    }
 }
@@ -0,0 +1,134 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use super::{ApiResponseItem, PullRequest, TextInput};
+
+pub const MERGE_METHODS: [&str; 3] = ["merge", "squash", "rebase"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeField {
+    Method,
+    Title,
+    Message,
+    DeleteBranch,
+}
+
+const MERGE_FIELDS: [MergeField; 4] = [
+    MergeField::Method,
+    MergeField::Title,
+    MergeField::Message,
+    MergeField::DeleteBranch,
+];
+
+// Options picked in the merge popup before the PR is merged
+pub struct MergeForm {
+    pub item: ApiResponseItem,
+    pub pull_request: PullRequest,
+    pub method: usize,
+    pub title: TextInput,
+    pub message: TextInput,
+    pub delete_branch: bool,
+    pub focus: MergeField,
+}
+
+impl MergeForm {
+    pub fn new(item: ApiResponseItem, pull_request: PullRequest) -> MergeForm {
+        let title = TextInput::new(&format!("{} (#{})", pull_request.title, pull_request.number));
+        MergeForm {
+            item,
+            pull_request,
+            method: 0,
+            title,
+            message: TextInput::multiline(),
+            delete_branch: false,
+            focus: MergeField::Method,
+        }
+    }
+
+    pub fn method(&self) -> &'static str {
+        MERGE_METHODS[self.method]
+    }
+
+    // A rebase keeps the commits as they are, so the title and message are hidden
+    fn fields(&self) -> Vec<MergeField> {
+        MERGE_FIELDS
+            .iter()
+            .copied()
+            .filter(|field| self.method() != "rebase" || !matches!(field, MergeField::Title | MergeField::Message))
+            .collect()
+    }
+
+    fn move_focus(&mut self, delta: isize) {
+        let fields = self.fields();
+        let index = fields.iter().position(|f| *f == self.focus).unwrap_or(0) as isize;
+        let next = (index + delta).rem_euclid(fields.len() as isize) as usize;
+        self.focus = fields[next];
+    }
+
+    // Field navigation and editing, ESC and ENTER are left to the caller
+    pub fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) {
+        match (code, self.focus) {
+            (KeyCode::Tab, _) | (KeyCode::Down, _) => self.move_focus(1),
+            (KeyCode::BackTab, _) | (KeyCode::Up, _) => self.move_focus(-1),
+            (KeyCode::Right, MergeField::Method) => self.method = (self.method + 1) % MERGE_METHODS.len(),
+            (KeyCode::Left, MergeField::Method) => self.method = (self.method + MERGE_METHODS.len() - 1) % MERGE_METHODS.len(),
+            (KeyCode::Char(' '), MergeField::DeleteBranch) if self.pull_request.head_in_base_repo() => {
+                self.delete_branch = !self.delete_branch;
+            }
+            (_, MergeField::Title) => {
+                self.title.handle_key(code, modifiers);
+            }
+            (_, MergeField::Message) => {
+                self.message.handle_key(code, modifiers);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn form() -> MergeForm {
+        MergeForm::new(ApiResponseItem::sample(1, "Fix"), PullRequest::sample(1))
+    }
+
+    fn focus_after(form: &mut MergeForm, code: KeyCode, times: usize) -> Vec<MergeField> {
+        (0..times)
+            .map(|_| {
+                form.handle_key(code, KeyModifiers::NONE);
+                form.focus
+            })
+            .collect()
+    }
+
+    #[test]
+    fn focus_goes_through_every_field() {
+        let mut form = form();
+        assert_eq!(
+            focus_after(&mut form, KeyCode::Tab, 4),
+            vec![MergeField::Title, MergeField::Message, MergeField::DeleteBranch, MergeField::Method]
+        );
+    }
+
+    #[test]
+    fn rebase_skips_the_title_and_message() {
+        let mut form = form();
+        form.handle_key(KeyCode::Left, KeyModifiers::NONE);
+        assert_eq!(form.method(), "rebase");
+        assert_eq!(focus_after(&mut form, KeyCode::Down, 2), vec![MergeField::DeleteBranch, MergeField::Method]);
+        assert_eq!(focus_after(&mut form, KeyCode::Up, 2), vec![MergeField::DeleteBranch, MergeField::Method]);
+    }
+
+    #[test]
+    fn title_stays_on_one_line_and_the_branch_toggles() {
+        let mut form = form();
+        form.focus = MergeField::Title;
+        form.handle_key(KeyCode::Enter, KeyModifiers::ALT);
+        assert_eq!(form.title.value, "Fix (#1)");
+
+        form.focus = MergeField::DeleteBranch;
+        form.handle_key(KeyCode::Char(' '), KeyModifiers::NONE);
+        assert!(form.delete_branch);
+    }
+}
@@ -3,6 +3,35 @@ pub mod api_response_item;
 pub mod issue_comments;
 pub mod user;
 pub mod label;
+pub mod pr_file;
+pub mod diff_line;
+pub mod diff_view;
+pub mod review_comment;
+pub mod pull_request;
+pub mod text_input;
+pub mod merge_form;
+pub mod ci_status;
+pub mod issue_tab;
+pub mod notification;
+pub mod inbox;
+pub mod config;
+pub mod filter;
+pub mod reactions;
+pub mod sort_mode;
+pub mod grouping;
+pub mod details_pane;
+pub mod timeline_event;
+pub mod timeline_view;
+pub mod reaction_picker;
+pub mod comment_filter;
+pub mod staleness;
+pub mod theme;
+pub mod action;
+pub mod key;
+pub mod keymap;
+pub mod help_view;
+pub mod priorities;
+pub mod popup;
 
 // Re-export the structs so they can be easily imported in other modules
 pub use api_response::ApiResponse;
@@ -10,3 +39,32 @@ pub use api_response_item::ApiResponseItem;
 pub use issue_comments::IssueComments;
 pub use user::User;
 pub use label::Label;
+pub use pr_file::PrFile;
+pub use diff_line::{DiffLine, DiffLineKind};
+pub use diff_view::DiffView;
+pub use review_comment::{DraftReviewComment, ReviewComment};
+pub use pull_request::{PullRequest, PullRequestReview};
+pub use text_input::TextInput;
+pub use merge_form::{MergeField, MergeForm, MERGE_METHODS};
+pub use ci_status::{CheckRuns, CiCheck, CiState, CiSummary, CombinedStatus};
+pub use issue_tab::{IssueTab, TabRow};
+pub use notification::Notification;
+pub use inbox::Inbox;
+pub use config::{Config, SavedSearch};
+pub use filter::Filter;
+pub use reactions::{Reactions, REACTION_KINDS};
+pub use sort_mode::SortMode;
+pub use grouping::Grouping;
+pub use details_pane::DetailsPane;
+pub use timeline_event::TimelineEvent;
+pub use timeline_view::TimelineView;
+pub use reaction_picker::ReactionPicker;
+pub use comment_filter::CommentFilter;
+pub use staleness::{StaleLevel, Staleness};
+pub use theme::{Theme, ThemeConfig};
+pub use action::Action;
+pub use key::Key;
+pub use keymap::{KeyContext, Keymap, KeysConfig};
+pub use help_view::HelpView;
+pub use priorities::{Priorities, PRIORITY_LEVELS};
+pub use popup::Popup;
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct NotificationSubject {
+    pub title: String,
+    pub url: Option<String>,
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct NotificationRepository {
+    pub full_name: String,
+    pub html_url: String,
+}
+
+// A notification thread from the /notifications API
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Notification {
+    pub id: String,
+    pub unread: bool,
+    pub reason: String,
+    pub updated_at: String,
+    pub subject: NotificationSubject,
+    pub repository: NotificationRepository,
+}
+
+impl Notification {
+    pub fn reason_label(&self) -> String {
+        match self.reason.as_str() {
+            "review_requested" => "review requested".to_string(),
+            "ci_activity" => "CI activity".to_string(),
+            "team_mention" => "team mention".to_string(),
+            "state_change" => "state change".to_string(),
+            reason => reason.replace('_', " "),
+        }
+    }
+
+    // Page of the subject on github.com, the API only gives its REST url
+    pub fn html_url(&self) -> String {
+        match &self.subject.url {
+            Some(url) => url
+                .replacen("https://api.github.com/repos/", "https://github.com/", 1)
+                .replacen("/pulls/", "/pull/", 1)
+                .replacen("/commits/", "/commit/", 1),
+            None => self.repository.html_url.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+impl Notification {
+    // Unread pull request notification, subject number 1 of the repository
+    pub fn sample(id: &str, repository: &str, updated_at: &str) -> Notification {
+        Notification {
+            id: id.to_string(),
+            unread: true,
+            reason: "review_requested".to_string(),
+            updated_at: updated_at.to_string(),
+            subject: NotificationSubject {
+                title: "Fix".to_string(),
+                url: Some(format!("https://api.github.com/repos/{}/pulls/1", repository)),
+                kind: "PullRequest".to_string(),
+            },
+            repository: NotificationRepository {
+                full_name: repository.to_string(),
+                html_url: format!("https://github.com/{}", repository),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subjects_open_on_github_com() {
+        let mut notification = Notification::sample("1", "org/app", "2024-01-01T00:00:00Z");
+        assert_eq!(notification.html_url(), "https://github.com/org/app/pull/1");
+
+        notification.subject.url = Some("https://api.github.com/repos/org/app/issues/2".to_string());
+        assert_eq!(notification.html_url(), "https://github.com/org/app/issues/2");
+
+        notification.subject.url = Some("https://api.github.com/repos/org/app/commits/abc".to_string());
+        assert_eq!(notification.html_url(), "https://github.com/org/app/commit/abc");
+
+        // Discussions have no url, their repository opens instead
+        notification.subject.url = None;
+        assert_eq!(notification.html_url(), "https://github.com/org/app");
+    }
+}
@@ -0,0 +1,87 @@
+use super::Action;
+
+// Popups drawn over a screen, they take the keys while they are open
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Popup {
+    Checks,
+    Timeline,
+    Reactions,
+    Review,
+    Filters,
+    // Organisations, repositories or labels to filter on
+    Picker,
+    // Closing the issue is only offered in Assignments
+    Actions { close_issue: bool },
+}
+
+impl Popup {
+    pub fn title(&self) -> &'static str {
+        match self {
+            Popup::Checks => "CI checks",
+            Popup::Timeline => "Timeline",
+            Popup::Reactions => "Reactions",
+            Popup::Review => "Submit review",
+            Popup::Filters => "Active filters",
+            Popup::Picker | Popup::Actions { .. } => "Actions",
+        }
+    }
+
+    // Actions the popup handles with what they do there, all of them also show their keys and close
+    pub fn actions(&self) -> Vec<(Action, &'static str)> {
+        let mut actions = match self {
+            Popup::Checks => vec![
+                (Action::MoveUp, "Previous check"),
+                (Action::MoveDown, "Next check"),
+                (Action::Confirm, "Open the log of the check"),
+            ],
+            Popup::Timeline => vec![
+                (Action::MoveUp, "Previous event"),
+                (Action::MoveDown, "Next event"),
+                (Action::Confirm, "Go to the linked item, or open it in the browser"),
+            ],
+            Popup::Reactions => vec![
+                (Action::MoveLeft, "Previous emoji"),
+                (Action::MoveRight, "Next emoji"),
+                (Action::MoveUp, "Previous issue or comment"),
+                (Action::MoveDown, "Next issue or comment"),
+                (Action::Confirm, "Send the reaction"),
+            ],
+            Popup::Review => vec![
+                (Action::MoveUp, "Previous kind of review"),
+                (Action::MoveDown, "Next kind of review"),
+                (Action::Confirm, "Write the summary and submit"),
+            ],
+            Popup::Filters => vec![
+                (Action::MoveUp, "Previous filter"),
+                (Action::MoveDown, "Next filter"),
+                (Action::Confirm, "Remove the selected filter"),
+            ],
+            Popup::Picker => vec![
+                (Action::MoveUp, "Previous entry"),
+                (Action::MoveDown, "Next entry"),
+                (Action::Confirm, "Filter on the selected entry"),
+            ],
+            Popup::Actions { .. } => vec![],
+        };
+        actions.push((Action::Help, "Show these keys"));
+        actions.push((Action::Cancel, "Close"));
+        actions
+    }
+
+    // Keys outside of the keymap, the digits of the actions popup
+    pub fn fixed_keys(&self) -> Vec<(&'static str, &'static str)> {
+        match self {
+            Popup::Actions { close_issue } => {
+                let mut keys = vec![];
+                if *close_issue {
+                    keys.push(("1", "Close the issue"));
+                }
+                keys.push(("2", "Filter on an organisation"));
+                keys.push(("3", "Filter on a repository"));
+                keys.push(("4", "Filter on a label"));
+                keys
+            }
+            _ => vec![],
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PrFile {
+    pub filename: String,
+    pub status: String,
+    pub additions: i32,
+    pub deletions: i32,
+    pub changes: i32,
+    // GitHub omits the patch for binary files and very large diffs
+    pub patch: Option<String>,
+}
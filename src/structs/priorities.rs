@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::{error::Error, fs, path::PathBuf};
+
+use super::Config;
+
+// P1 is the most urgent, items without a priority come after P3
+pub const PRIORITY_LEVELS: u8 = 3;
+
+// Priorities given to items in the app, by URL. They stay on this machine, in priorities.toml next to config.toml
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct Priorities {
+    #[serde(default)]
+    pub items: HashMap<String, u8>,
+}
+
+impl Priorities {
+    fn path() -> Option<PathBuf> {
+        Config::path().and_then(|path| path.parent().map(|dir| dir.join("priorities.toml")))
+    }
+
+    // A missing file means no priorities yet, an invalid one is an error
+    pub fn load() -> Result<Priorities, Box<dyn Error>> {
+        let path = match Priorities::path() {
+            Some(path) if path.exists() => path,
+            _ => return Ok(Priorities::default()),
+        };
+        let content = fs::read_to_string(&path)?;
+        let priorities: Priorities = toml::from_str(&content)
+            .map_err(|e| format!("Invalid priorities {}: {}", path.display(), e))?;
+        if let Some((url, priority)) = priorities.items.iter().find(|(_, p)| !(1..=PRIORITY_LEVELS).contains(*p)) {
+            return Err(format!("Invalid priority {} for {} in {}, expected 1 to {}", priority, url, path.display(), PRIORITY_LEVELS).into());
+        }
+        Ok(priorities)
+    }
+
+    // The file is only written by the app, so it is rewritten as a whole
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let path = Priorities::path().ok_or("No config directory found")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, url: &str) -> Option<u8> {
+        self.items.get(url).copied()
+    }
+
+    // Next priority of the item: none, P1, P2, P3, then none again
+    pub fn cycle(&mut self, url: &str) -> Option<u8> {
+        let next = match self.get(url) {
+            None => Some(1),
+            Some(priority) if priority < PRIORITY_LEVELS => Some(priority + 1),
+            Some(_) => None,
+        };
+        match next {
+            Some(priority) => self.items.insert(url.to_string(), priority),
+            None => self.items.remove(url),
+        };
+        next
+    }
+}
@@ -0,0 +1,256 @@
+use super::User;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PullRequestRepo {
+    pub full_name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PullRequestRef {
+    #[serde(rename = "ref")]
+    pub branch: String,
+    pub sha: String,
+    pub label: String,
+    // None when the fork the PR comes from has been deleted
+    pub repo: Option<PullRequestRepo>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Team {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PullRequestReview {
+    pub user: User,
+    // APPROVED, CHANGES_REQUESTED, COMMENTED, DISMISSED or PENDING
+    pub state: String,
+    pub submitted_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PullRequest {
+    pub number: i32,
+    pub title: String,
+    pub user: User,
+    pub head: PullRequestRef,
+    pub base: PullRequestRef,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub merged: bool,
+    // null while GitHub is still computing whether the PR can be merged
+    pub mergeable: Option<bool>,
+    #[serde(default)]
+    pub mergeable_state: String,
+    #[serde(default)]
+    pub requested_reviewers: Vec<User>,
+    #[serde(default)]
+    pub requested_teams: Vec<Team>,
+    #[serde(default)]
+    pub additions: i32,
+    #[serde(default)]
+    pub deletions: i32,
+    #[serde(default)]
+    pub changed_files: i32,
+    #[serde(default)]
+    pub commits: i32,
+    // Filled from the reviews endpoint, not part of the pull request payload
+    #[serde(skip_deserializing)]
+    pub reviews: Vec<PullRequestReview>,
+}
+
+impl PullRequest {
+    // GitHub computes mergeability in the background, it is unknown until that job is done
+    pub fn mergeability_pending(&self) -> bool {
+        !self.merged && (self.mergeable.is_none() || self.mergeable_state == "unknown")
+    }
+
+    // Human readable mergeability, and whether it should block the merge form from opening
+    pub fn mergeability(&self) -> (String, bool) {
+        if self.merged {
+            return ("This pull request is already merged".to_string(), true);
+        }
+        match (self.mergeable, self.mergeable_state.as_str()) {
+            _ if self.mergeability_pending() => (
+                "GitHub is still checking whether this pull request can be merged, try again in a moment".to_string(),
+                true,
+            ),
+            (Some(false), _) | (_, "dirty") => (
+                format!("Merge conflicts between {} and {}, resolve them before merging", self.head.label, self.base.label),
+                true,
+            ),
+            (_, "draft") => ("This pull request is still a draft".to_string(), true),
+            (_, "blocked") => ("Blocked: required reviews or status checks are not satisfied".to_string(), false),
+            (_, "behind") => (format!("The head branch is behind {}", self.base.branch), false),
+            (_, "unstable") => ("Mergeable, but some non required checks are failing".to_string(), false),
+            _ => ("Ready to merge".to_string(), false),
+        }
+    }
+
+    // Latest decisive review of each reviewer
+    fn latest_reviews(&self) -> Vec<(String, String)> {
+        let mut states: Vec<(String, String)> = vec![];
+        for review in self.reviews.iter().filter(|r| r.state != "PENDING") {
+            match states.iter_mut().find(|(login, _)| *login == review.user.login) {
+                // A comment does not replace an approval or a change request
+                Some((_, state)) if review.state == "COMMENTED" && state != "COMMENTED" => {}
+                Some((_, state)) => *state = review.state.clone(),
+                None => states.push((review.user.login.clone(), review.state.clone())),
+            }
+        }
+        states
+    }
+
+    // Latest decisive review of each reviewer, followed by the reviewers still to review
+    pub fn reviewer_states(&self) -> Vec<(String, String)> {
+        let mut states = self.latest_reviews();
+        for reviewer in &self.requested_reviewers {
+            // Requested again after reviewing, the previous review no longer counts
+            states.retain(|(login, _)| *login != reviewer.login);
+            states.push((reviewer.login.clone(), "REVIEW_REQUESTED".to_string()));
+        }
+        for team in &self.requested_teams {
+            states.push((format!("@{}", team.name), "REVIEW_REQUESTED".to_string()));
+        }
+        states
+    }
+
+    // A change request stands until its author approves, even when they are asked to review again,
+    // and an approval only counts once no review is pending
+    pub fn review_decision(&self) -> &'static str {
+        let states = self.latest_reviews();
+        let pending = !self.requested_reviewers.is_empty() || !self.requested_teams.is_empty();
+        if states.iter().any(|(_, state)| state == "CHANGES_REQUESTED") {
+            "Changes requested"
+        } else if !pending && states.iter().any(|(_, state)| state == "APPROVED") {
+            "Approved"
+        } else {
+            "Review required"
+        }
+    }
+
+    // Branches of forks cannot be deleted from the base repository
+    pub fn head_in_base_repo(&self) -> bool {
+        match (&self.head.repo, &self.base.repo) {
+            (Some(head), Some(base)) => head.full_name == base.full_name,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+impl PullRequest {
+    // Open pull request of org/app from a branch of the same repository, ready to merge
+    pub fn sample(number: i32) -> PullRequest {
+        let branch = |name: &str| PullRequestRef {
+            branch: name.to_string(),
+            sha: "abc".to_string(),
+            label: format!("org:{}", name),
+            repo: Some(PullRequestRepo { full_name: "org/app".to_string() }),
+        };
+        PullRequest {
+            number,
+            title: "Fix".to_string(),
+            user: User { login: "author".to_string() },
+            head: branch("fix"),
+            base: branch("main"),
+            draft: false,
+            merged: false,
+            mergeable: Some(true),
+            mergeable_state: "clean".to_string(),
+            requested_reviewers: vec![],
+            requested_teams: vec![],
+            additions: 0,
+            deletions: 0,
+            changed_files: 0,
+            commits: 0,
+            reviews: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pull_request(reviews: &[(&str, &str)], requested: &[&str], teams: &[&str]) -> PullRequest {
+        let user = |login: &str| User { login: login.to_string() };
+        let mut pull_request = PullRequest::sample(1);
+        pull_request.requested_reviewers = requested.iter().map(|login| user(login)).collect();
+        pull_request.requested_teams = teams.iter().map(|name| Team { name: name.to_string() }).collect();
+        pull_request.reviews = reviews
+            .iter()
+            .map(|(login, state)| PullRequestReview { user: user(login), state: state.to_string(), submitted_at: None })
+            .collect();
+        pull_request
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected.iter().map(|(login, state)| (login.to_string(), state.to_string())).collect()
+    }
+
+    #[test]
+    fn comments_do_not_replace_a_decisive_review() {
+        let pr = pull_request(&[("alice", "APPROVED"), ("alice", "COMMENTED"), ("bob", "COMMENTED"), ("carol", "PENDING")], &[], &[]);
+        assert_eq!(pr.reviewer_states(), pairs(&[("alice", "APPROVED"), ("bob", "COMMENTED")]));
+    }
+
+    #[test]
+    fn later_decisive_reviews_replace_earlier_ones() {
+        let pr = pull_request(&[("alice", "CHANGES_REQUESTED"), ("alice", "APPROVED")], &[], &[]);
+        assert_eq!(pr.reviewer_states(), pairs(&[("alice", "APPROVED")]));
+        assert_eq!(pr.review_decision(), "Approved");
+    }
+
+    #[test]
+    fn requested_reviewers_and_teams_come_last() {
+        let pr = pull_request(&[("alice", "APPROVED"), ("bob", "APPROVED")], &["alice"], &["core"]);
+        assert_eq!(pr.reviewer_states(), pairs(&[("bob", "APPROVED"), ("alice", "REVIEW_REQUESTED"), ("@core", "REVIEW_REQUESTED")]));
+    }
+
+    #[test]
+    fn change_request_stands_when_asked_to_review_again() {
+        let pr = pull_request(&[("alice", "CHANGES_REQUESTED"), ("bob", "APPROVED")], &["alice"], &[]);
+        assert_eq!(pr.review_decision(), "Changes requested");
+    }
+
+    #[test]
+    fn approval_waits_for_pending_reviews() {
+        assert_eq!(pull_request(&[("alice", "APPROVED")], &["bob"], &[]).review_decision(), "Review required");
+        assert_eq!(pull_request(&[("alice", "APPROVED")], &[], &["core"]).review_decision(), "Review required");
+        assert_eq!(pull_request(&[], &[], &[]).review_decision(), "Review required");
+        assert_eq!(pull_request(&[("alice", "APPROVED")], &[], &[]).review_decision(), "Approved");
+    }
+
+    fn mergeability(mergeable: Option<bool>, state: &str) -> (String, bool) {
+        let mut pull_request = PullRequest::sample(1);
+        pull_request.mergeable = mergeable;
+        pull_request.mergeable_state = state.to_string();
+        pull_request.mergeability()
+    }
+
+    #[test]
+    fn mergeability_blocks_the_form_only_when_merging_cannot_work() {
+        assert_eq!(mergeability(Some(true), "clean"), ("Ready to merge".to_string(), false));
+        assert!(!mergeability(Some(true), "blocked").1);
+        assert!(mergeability(Some(false), "dirty").1);
+        assert!(mergeability(Some(false), "dirty").0.contains("org:fix"));
+        assert!(mergeability(Some(true), "draft").1);
+    }
+
+    #[test]
+    fn mergeability_is_pending_until_github_computes_it() {
+        let (message, blocked) = mergeability(None, "unknown");
+        assert!(blocked);
+        assert!(message.contains("still checking"), "{}", message);
+        assert!(mergeability(Some(true), "unknown").1);
+
+        let mut merged = PullRequest::sample(1);
+        merged.merged = true;
+        merged.mergeable = None;
+        assert!(!merged.mergeability_pending());
+        assert_eq!(merged.mergeability(), ("This pull request is already merged".to_string(), true));
+    }
+}
@@ -0,0 +1,90 @@
+use tui::widgets::ListState;
+
+use super::{ApiResponseItem, REACTION_KINDS};
+
+// Popup to react to an issue or one of its comments: targets go up and down, emojis left and right
+pub struct ReactionPicker {
+    pub item: ApiResponseItem,
+    // 0 is the issue itself, then the comments in order
+    pub target_state: ListState,
+    pub emoji: usize,
+}
+
+impl ReactionPicker {
+    pub fn new(item: ApiResponseItem) -> ReactionPicker {
+        let mut target_state = ListState::default();
+        target_state.select(Some(0));
+        ReactionPicker { item, target_state, emoji: 0 }
+    }
+
+    pub fn target_count(&self) -> usize {
+        self.item.comments_list.len() + 1
+    }
+
+    pub fn move_target(&mut self, delta: isize) {
+        let selected = self.target_state.selected().unwrap_or(0) as isize;
+        let next = (selected + delta).clamp(0, self.target_count() as isize - 1);
+        self.target_state.select(Some(next as usize));
+    }
+
+    pub fn move_emoji(&mut self, delta: isize) {
+        self.emoji = (self.emoji as isize + delta).rem_euclid(REACTION_KINDS.len() as isize) as usize;
+    }
+
+    pub fn content(&self) -> &'static str {
+        REACTION_KINDS[self.emoji].0
+    }
+
+    // Id of the selected comment, None when the issue itself is selected
+    pub fn comment_id(&self) -> Option<u64> {
+        match self.target_state.selected() {
+            Some(0) | None => None,
+            Some(i) => self.item.comments_list.get(i - 1).map(|comment| comment.id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::{IssueComments, User};
+
+    fn picker() -> ReactionPicker {
+        let mut item = ApiResponseItem::sample(1, "Fix");
+        item.comments_list = [11, 12]
+            .iter()
+            .map(|&id| IssueComments {
+                id,
+                body: String::new(),
+                user: User { login: "bob".to_string() },
+                created_at: String::new(),
+                updated_at: String::new(),
+                author_association: String::new(),
+                reactions: None,
+                html_url: String::new(),
+            })
+            .collect();
+        ReactionPicker::new(item)
+    }
+
+    #[test]
+    fn comment_id_is_none_on_the_issue() {
+        let mut picker = picker();
+        assert_eq!(picker.comment_id(), None);
+        picker.move_target(1);
+        assert_eq!(picker.comment_id(), Some(11));
+        picker.move_target(5);
+        assert_eq!(picker.comment_id(), Some(12));
+        picker.move_target(-5);
+        assert_eq!(picker.comment_id(), None);
+    }
+
+    #[test]
+    fn emojis_wrap_around() {
+        let mut picker = picker();
+        picker.move_emoji(-1);
+        assert_eq!(picker.content(), "eyes");
+        picker.move_emoji(1);
+        assert_eq!(picker.content(), "+1");
+    }
+}
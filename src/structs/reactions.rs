@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+// Reaction counts GitHub attaches to issues and comments
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct Reactions {
+    pub total_count: i32,
+    #[serde(rename = "+1", default)]
+    pub plus_one: i32,
+    #[serde(rename = "-1", default)]
+    pub minus_one: i32,
+    #[serde(default)]
+    pub laugh: i32,
+    #[serde(default)]
+    pub hooray: i32,
+    #[serde(default)]
+    pub confused: i32,
+    #[serde(default)]
+    pub heart: i32,
+    #[serde(default)]
+    pub rocket: i32,
+    #[serde(default)]
+    pub eyes: i32,
+}
+
+// Content names of the reactions API with their emoji, in GitHub's order
+pub const REACTION_KINDS: [(&str, &str); 8] = [
+    ("+1", "👍"),
+    ("-1", "👎"),
+    ("laugh", "😄"),
+    ("hooray", "🎉"),
+    ("confused", "😕"),
+    ("heart", "❤️"),
+    ("rocket", "🚀"),
+    ("eyes", "👀"),
+];
+
+impl Reactions {
+    pub fn count(&self, content: &str) -> i32 {
+        match content {
+            "+1" => self.plus_one,
+            "-1" => self.minus_one,
+            "laugh" => self.laugh,
+            "hooray" => self.hooray,
+            "confused" => self.confused,
+            "heart" => self.heart,
+            "rocket" => self.rocket,
+            "eyes" => self.eyes,
+            _ => 0,
+        }
+    }
+
+    pub fn add(&mut self, content: &str) {
+        let count = match content {
+            "+1" => &mut self.plus_one,
+            "-1" => &mut self.minus_one,
+            "laugh" => &mut self.laugh,
+            "hooray" => &mut self.hooray,
+            "confused" => &mut self.confused,
+            "heart" => &mut self.heart,
+            "rocket" => &mut self.rocket,
+            "eyes" => &mut self.eyes,
+            _ => return,
+        };
+        *count += 1;
+        self.total_count += 1;
+    }
+
+    // e.g. "👍 3  🎉 1", empty when nobody reacted
+    pub fn summary(&self) -> String {
+        REACTION_KINDS
+            .iter()
+            .filter(|(content, _)| self.count(content) > 0)
+            .map(|(content, emoji)| format!("{} {}", emoji, self.count(content)))
+            .collect::<Vec<_>>()
+            .join("  ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn added_reactions_are_counted() {
+        let mut reactions = Reactions::default();
+        reactions.add("hooray");
+        reactions.add("+1");
+        reactions.add("+1");
+        reactions.add("unknown");
+        assert_eq!(reactions.count("+1"), 2);
+        assert_eq!(reactions.count("hooray"), 1);
+        assert_eq!(reactions.count("unknown"), 0);
+        assert_eq!(reactions.total_count, 3);
+    }
+
+    #[test]
+    fn summary_follows_github_order() {
+        assert_eq!(Reactions::default().summary(), "");
+        let mut reactions = Reactions::default();
+        for content in ["eyes", "hooray", "+1", "hooray"] {
+            reactions.add(content);
+        }
+        assert_eq!(reactions.summary(), "👍 1  🎉 2  👀 1");
+    }
+}
@@ -0,0 +1,32 @@
+use super::User;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ReviewComment {
+    pub id: i64,
+    pub body: String,
+    pub user: User,
+    pub path: String,
+    // None when the comment is outdated and no longer anchored to the current diff
+    pub line: Option<i32>,
+    pub side: Option<String>,
+    pub in_reply_to_id: Option<i64>,
+    pub html_url: String,
+    #[serde(skip_deserializing)]
+    pub resolved: bool,
+}
+
+impl ReviewComment {
+    // Replies are attached to the first comment of their thread
+    pub fn thread_id(&self) -> i64 {
+        self.in_reply_to_id.unwrap_or(self.id)
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DraftReviewComment {
+    pub path: String,
+    pub line: i32,
+    pub side: String,
+    pub body: String,
+}
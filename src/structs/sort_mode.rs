@@ -0,0 +1,135 @@
+use std::cmp::Ordering;
+
+use super::{ApiResponseItem, Priorities, StaleLevel, Staleness, PRIORITY_LEVELS};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortMode {
+    Updated,
+    Created,
+    Repository,
+    Number,
+    Comments,
+    Staleness,
+    Reactions,
+    Priority,
+}
+
+pub const SORT_MODES: [SortMode; 8] = [
+    SortMode::Updated,
+    SortMode::Created,
+    SortMode::Repository,
+    SortMode::Number,
+    SortMode::Comments,
+    SortMode::Staleness,
+    SortMode::Reactions,
+    SortMode::Priority,
+];
+
+impl SortMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortMode::Updated => "updated",
+            SortMode::Created => "created",
+            SortMode::Repository => "repository",
+            SortMode::Number => "number",
+            SortMode::Comments => "comments",
+            SortMode::Staleness => "staleness",
+            SortMode::Reactions => "reactions",
+            SortMode::Priority => "priority",
+        }
+    }
+
+    pub fn next(&self) -> SortMode {
+        let index = SORT_MODES.iter().position(|mode| mode == self).unwrap_or(0);
+        SORT_MODES[(index + 1) % SORT_MODES.len()]
+    }
+
+    // Ascending order of the mode, the tab reverses it for descending.
+    // Staleness grows with the level given by the thresholds, then with the time since the last update.
+    // Priority grows with urgency, items without one are the least urgent
+    pub fn compare(&self, a: &ApiResponseItem, b: &ApiResponseItem, staleness: &Staleness, priorities: &Priorities) -> Ordering {
+        match self {
+            SortMode::Updated => a.updated_at.cmp(&b.updated_at),
+            SortMode::Created => a.created_at.cmp(&b.created_at),
+            SortMode::Repository => (&a.organization, &a.repository, a.number).cmp(&(&b.organization, &b.repository, b.number)),
+            SortMode::Number => a.number.cmp(&b.number),
+            SortMode::Comments => a.comments.cmp(&b.comments),
+            SortMode::Staleness => {
+                let level = |item: &ApiResponseItem| match staleness.level(item) {
+                    StaleLevel::Fresh => 0,
+                    StaleLevel::Aging => 1,
+                    StaleLevel::Stale => 2,
+                };
+                level(a).cmp(&level(b)).then_with(|| b.updated_at.cmp(&a.updated_at))
+            }
+            SortMode::Reactions => {
+                let count = |item: &ApiResponseItem| item.reactions.as_ref().map(|r| r.total_count).unwrap_or(0);
+                count(a).cmp(&count(b))
+            }
+            SortMode::Priority => {
+                let urgency = |item: &ApiResponseItem| priorities.get(&item.url).map(|p| PRIORITY_LEVELS + 1 - p).unwrap_or(0);
+                urgency(a).cmp(&urgency(b))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use crate::structs::staleness::Thresholds;
+
+    fn updated_days_ago(number: i32, days: i64) -> ApiResponseItem {
+        let mut item = ApiResponseItem::sample(number, "Item");
+        item.updated_at = (Utc::now() - Duration::days(days)).format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        item
+    }
+
+    fn sorted(mode: SortMode, items: &[ApiResponseItem], staleness: &Staleness, priorities: &Priorities) -> Vec<i32> {
+        let mut items = items.to_vec();
+        items.sort_by(|a, b| mode.compare(a, b, staleness, priorities));
+        items.iter().map(|item| item.number).collect()
+    }
+
+    #[test]
+    fn staleness_ranks_the_level_before_the_age() {
+        let mut staleness = Staleness { warning_days: 10, stale_days: 20, ..Staleness::default() };
+        staleness.labels.insert("urgent".to_string(), Thresholds { warning_days: 1, stale_days: 2 });
+        let items = [
+            updated_days_ago(1, 15),
+            updated_days_ago(2, 5),
+            // Updated more recently than 1, but stale for its label
+            updated_days_ago(3, 3).with_labels(&["Urgent"]),
+            updated_days_ago(4, 30),
+            updated_days_ago(5, 1),
+        ];
+        assert_eq!(sorted(SortMode::Staleness, &items, &staleness, &Priorities::default()), vec![5, 2, 1, 3, 4]);
+    }
+
+    #[test]
+    fn priority_puts_items_without_one_first_in_ascending_order() {
+        let items: Vec<ApiResponseItem> = (1..=4).map(|number| ApiResponseItem::sample(number, "Item")).collect();
+        let mut priorities = Priorities::default();
+        priorities.items.insert(items[0].url.clone(), 3);
+        priorities.items.insert(items[1].url.clone(), 1);
+        priorities.items.insert(items[3].url.clone(), 2);
+        assert_eq!(sorted(SortMode::Priority, &items, &Staleness::default(), &priorities), vec![3, 1, 4, 2]);
+    }
+
+    #[test]
+    fn repository_orders_by_organization_then_number() {
+        let mut items: Vec<ApiResponseItem> = [3, 1, 2].iter().map(|number| ApiResponseItem::sample(*number, "Item")).collect();
+        items[0].organization = Some("another".to_string());
+        assert_eq!(sorted(SortMode::Repository, &items, &Staleness::default(), &Priorities::default()), vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn next_goes_through_every_mode() {
+        let mut mode = SortMode::Updated;
+        for expected in SORT_MODES.iter().skip(1).chain(SORT_MODES.iter().take(1)) {
+            mode = mode.next();
+            assert_eq!(mode, *expected);
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use tui::style::Color;
+
+use super::{ApiResponseItem, Theme};
+use crate::utils::parse_color;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StaleLevel {
+    Fresh,
+    Aging,
+    Stale,
+}
+
+// Days without update after which an item is aging, then stale
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Thresholds {
+    pub warning_days: i64,
+    pub stale_days: i64,
+}
+
+// Items matching the label and repository ("org/repo") of the rule, and created more than
+// `older_than_days` ago, are shown in the color of the rule and count as stale
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct SlaRule {
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub repo: Option<String>,
+    pub older_than_days: i64,
+    #[serde(default = "default_sla_color")]
+    pub color: String,
+}
+
+fn default_sla_color() -> String {
+    "red".to_string()
+}
+
+fn default_warning_days() -> i64 {
+    60
+}
+
+fn default_stale_days() -> i64 {
+    90
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Staleness {
+    #[serde(default = "default_warning_days")]
+    pub warning_days: i64,
+    #[serde(default = "default_stale_days")]
+    pub stale_days: i64,
+    // Overrides by label name, case insensitive like GitHub labels. The strictest one wins when an item has several
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub labels: HashMap<String, Thresholds>,
+    // Overrides by repository, e.g. "org/repo", they take precedence over labels
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub repos: HashMap<String, Thresholds>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<SlaRule>,
+}
+
+impl Default for Staleness {
+    fn default() -> Staleness {
+        Staleness {
+            warning_days: default_warning_days(),
+            stale_days: default_stale_days(),
+            labels: HashMap::new(),
+            repos: HashMap::new(),
+            rules: vec![],
+        }
+    }
+}
+
+fn days_since(timestamp: &str) -> Option<i64> {
+    timestamp
+        .parse::<DateTime<Utc>>()
+        .ok()
+        .map(|date| Utc::now().signed_duration_since(date).num_days())
+}
+
+fn full_name(item: &ApiResponseItem) -> String {
+    format!("{}/{}", item.organization.as_deref().unwrap_or_default(), item.repository.as_deref().unwrap_or_default())
+}
+
+impl Staleness {
+    // Invalid rule colors stop the app at startup, like invalid theme colors
+    pub fn validate(&self) -> Result<(), Box<dyn Error>> {
+        for rule in &self.rules {
+            parse_color(&rule.color).ok_or_else(|| format!("Invalid color \"{}\" for a staleness rule", rule.color))?;
+        }
+        Ok(())
+    }
+
+    fn thresholds(&self, item: &ApiResponseItem) -> Thresholds {
+        if let Some(thresholds) = self.repos.get(&full_name(item)) {
+            return thresholds.clone();
+        }
+        item.labels
+            .iter()
+            .flat_map(|label| {
+                self.labels
+                    .iter()
+                    .filter(move |(name, _)| name.eq_ignore_ascii_case(&label.name))
+                    .map(|(_, thresholds)| thresholds)
+            })
+            .min_by_key(|thresholds| thresholds.stale_days)
+            .cloned()
+            .unwrap_or(Thresholds { warning_days: self.warning_days, stale_days: self.stale_days })
+    }
+
+    pub fn sla_rule(&self, item: &ApiResponseItem) -> Option<&SlaRule> {
+        let age = days_since(&item.created_at)?;
+        self.rules.iter().find(|rule| {
+            age > rule.older_than_days
+                && rule.label.as_ref().map(|name| item.labels.iter().any(|l| l.name.eq_ignore_ascii_case(name))).unwrap_or(true)
+                && rule.repo.as_ref().map(|repo| *repo == full_name(item)).unwrap_or(true)
+        })
+    }
+
+    // How long the item has gone without update, a broken SLA rule makes it stale
+    pub fn level(&self, item: &ApiResponseItem) -> StaleLevel {
+        if self.sla_rule(item).is_some() {
+            return StaleLevel::Stale;
+        }
+        let thresholds = self.thresholds(item);
+        match days_since(&item.updated_at) {
+            Some(days) if days > thresholds.stale_days => StaleLevel::Stale,
+            Some(days) if days > thresholds.warning_days => StaleLevel::Aging,
+            _ => StaleLevel::Fresh,
+        }
+    }
+
+    pub fn color(&self, item: &ApiResponseItem, theme: &Theme) -> Color {
+        if let Some(color) = self.sla_rule(item).and_then(|rule| parse_color(&rule.color)) {
+            return color;
+        }
+        match self.level(item) {
+            StaleLevel::Stale => theme.stale,
+            StaleLevel::Aging => theme.aging,
+            StaleLevel::Fresh => theme.text,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn days_ago(days: i64) -> String {
+        (Utc::now() - Duration::days(days)).format("%Y-%m-%dT%H:%M:%SZ").to_string()
+    }
+
+    // Item of org/app created and last updated that many days ago
+    fn item(created: i64, updated: i64, labels: &[&str]) -> ApiResponseItem {
+        let mut item = ApiResponseItem::sample(1, "Item").with_labels(labels);
+        item.created_at = days_ago(created);
+        item.updated_at = days_ago(updated);
+        item
+    }
+
+    fn rule(label: Option<&str>, repo: Option<&str>, older_than_days: i64, color: &str) -> SlaRule {
+        SlaRule {
+            label: label.map(str::to_string),
+            repo: repo.map(str::to_string),
+            older_than_days,
+            color: color.to_string(),
+        }
+    }
+
+    #[test]
+    fn level_follows_the_default_thresholds() {
+        let staleness = Staleness::default();
+        assert_eq!(staleness.level(&item(200, 10, &[])), StaleLevel::Fresh);
+        assert_eq!(staleness.level(&item(200, 61, &[])), StaleLevel::Aging);
+        assert_eq!(staleness.level(&item(200, 91, &[])), StaleLevel::Stale);
+    }
+
+    #[test]
+    fn strictest_label_wins_and_repos_take_precedence() {
+        let mut staleness = Staleness::default();
+        staleness.labels.insert("bug".to_string(), Thresholds { warning_days: 3, stale_days: 7 });
+        staleness.labels.insert("Security".to_string(), Thresholds { warning_days: 1, stale_days: 2 });
+        assert_eq!(staleness.level(&item(10, 5, &["BUG"])), StaleLevel::Aging);
+        assert_eq!(staleness.level(&item(10, 5, &["bug", "security"])), StaleLevel::Stale);
+
+        staleness.repos.insert("org/app".to_string(), Thresholds { warning_days: 120, stale_days: 180 });
+        assert_eq!(staleness.level(&item(10, 5, &["bug", "security"])), StaleLevel::Fresh);
+    }
+
+    #[test]
+    fn first_matching_sla_rule_applies() {
+        let staleness = Staleness {
+            rules: vec![
+                rule(Some("Bug"), None, 7, "red"),
+                rule(None, Some("org/app"), 30, "#ff8800"),
+                rule(None, Some("org/other"), 0, "blue"),
+            ],
+            ..Staleness::default()
+        };
+        let color = |item: &ApiResponseItem| staleness.sla_rule(item).map(|rule| rule.color.clone());
+        assert_eq!(color(&item(8, 0, &["bug"])), Some("red".to_string()));
+        assert_eq!(color(&item(31, 0, &["bug"])), Some("red".to_string()));
+        assert_eq!(color(&item(31, 0, &[])), Some("#ff8800".to_string()));
+        assert_eq!(color(&item(6, 0, &["bug"])), None);
+    }
+
+    #[test]
+    fn broken_sla_rule_makes_the_item_stale() {
+        let staleness = Staleness { rules: vec![rule(Some("bug"), None, 7, "red")], ..Staleness::default() };
+        assert_eq!(staleness.level(&item(8, 0, &["bug"])), StaleLevel::Stale);
+        assert_eq!(staleness.level(&item(8, 0, &[])), StaleLevel::Fresh);
+    }
+
+    #[test]
+    fn invalid_rule_colors_are_rejected() {
+        let valid = Staleness { rules: vec![rule(None, None, 1, "lightblue"), rule(None, None, 1, "#d73a4a")], ..Staleness::default() };
+        assert!(valid.validate().is_ok());
+        let invalid = Staleness { rules: vec![rule(None, None, 1, "reddish")], ..Staleness::default() };
+        assert!(invalid.validate().is_err());
+    }
+}
@@ -0,0 +1,118 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+
+// Single buffer text field edited from key events, the cursor is a char index
+#[derive(Debug, Clone, Default)]
+pub struct TextInput {
+    pub value: String,
+    pub cursor: usize,
+    // Comments take new lines with ALT + ENTER, queries and names stay on one line
+    pub multiline: bool,
+}
+
+impl TextInput {
+    pub fn new(value: &str) -> TextInput {
+        TextInput {
+            value: value.to_string(),
+            cursor: value.chars().count(),
+            multiline: false,
+        }
+    }
+
+    pub fn multiline() -> TextInput {
+        TextInput { multiline: true, ..TextInput::default() }
+    }
+
+    fn byte_index(&self) -> usize {
+        self.value
+            .char_indices()
+            .nth(self.cursor)
+            .map(|(i, _)| i)
+            .unwrap_or(self.value.len())
+    }
+
+    pub fn insert(&mut self, c: char) {
+        let index = self.byte_index();
+        self.value.insert(index, c);
+        self.cursor += 1;
+    }
+
+    pub fn backspace(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            let index = self.byte_index();
+            self.value.remove(index);
+        }
+    }
+
+    pub fn delete(&mut self) {
+        if self.cursor < self.value.chars().count() {
+            let index = self.byte_index();
+            self.value.remove(index);
+        }
+    }
+
+    // Apply an editing key, returns false when the key is not handled by the input.
+    // Chars typed with CONTROL or ALT are shortcuts, not text
+    pub fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        let shortcut = modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        match code {
+            KeyCode::Enter if self.multiline && modifiers.contains(KeyModifiers::ALT) => self.insert('\n'),
+            KeyCode::Char(c) if !shortcut && (self.multiline || c != '\n') => self.insert(c),
+            KeyCode::Backspace => self.backspace(),
+            KeyCode::Delete => self.delete(),
+            KeyCode::Left => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Right => self.cursor = (self.cursor + 1).min(self.value.chars().count()),
+            KeyCode::Home => self.cursor = 0,
+            KeyCode::End => self.cursor = self.value.chars().count(),
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn typed(input: &mut TextInput, keys: &[(KeyCode, KeyModifiers)]) -> Vec<bool> {
+        keys.iter().map(|(code, modifiers)| input.handle_key(*code, *modifiers)).collect()
+    }
+
+    #[test]
+    fn edits_at_the_cursor() {
+        let mut input = TextInput::new("ac");
+        typed(&mut input, &[(KeyCode::Left, KeyModifiers::NONE), (KeyCode::Char('b'), KeyModifiers::NONE)]);
+        assert_eq!((input.value.as_str(), input.cursor), ("abc", 2));
+        typed(&mut input, &[(KeyCode::Home, KeyModifiers::NONE), (KeyCode::Delete, KeyModifiers::NONE), (KeyCode::End, KeyModifiers::NONE), (KeyCode::Backspace, KeyModifiers::NONE)]);
+        assert_eq!((input.value.as_str(), input.cursor), ("b", 1));
+    }
+
+    #[test]
+    fn shortcuts_are_not_typed() {
+        let mut input = TextInput::multiline();
+        let handled = typed(&mut input, &[
+            (KeyCode::Char('a'), KeyModifiers::CONTROL),
+            (KeyCode::Char('x'), KeyModifiers::ALT),
+            (KeyCode::Char('A'), KeyModifiers::SHIFT),
+        ]);
+        assert_eq!(handled, vec![false, false, true]);
+        assert_eq!(input.value, "A");
+    }
+
+    #[test]
+    fn only_multiline_inputs_take_new_lines() {
+        let mut single = TextInput::default();
+        let mut multi = TextInput::multiline();
+        for input in [&mut single, &mut multi] {
+            typed(input, &[
+                (KeyCode::Char('a'), KeyModifiers::NONE),
+                (KeyCode::Enter, KeyModifiers::ALT),
+                (KeyCode::Char('\n'), KeyModifiers::NONE),
+                (KeyCode::Enter, KeyModifiers::NONE),
+                (KeyCode::Char('b'), KeyModifiers::NONE),
+            ]);
+        }
+        assert_eq!(single.value, "ab");
+        assert_eq!(multi.value, "a\n\nb");
+    }
+}
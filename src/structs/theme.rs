@@ -0,0 +1,215 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use tui::style::{Color, Style};
+
+use crate::utils::parse_color;
+
+pub const THEME_NAMES: [&str; 3] = ["dark", "light", "high-contrast"];
+
+// Colors of the UI by role, every render function picks its colors here
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    // Regular text
+    pub text: Color,
+    // Section titles, key hints, group headers and the border of the focused pane
+    pub accent: Color,
+    // Text drawn on top of the accent color, e.g. the filter chips
+    pub on_accent: Color,
+    pub border: Color,
+    // Less important text: ages, kinds, reaction summaries
+    pub secondary: Color,
+    // Text pushed to the background: resolved threads, metadata, separators
+    pub muted: Color,
+    // Highlighted entry of the popups and chars matched by the fuzzy filter
+    pub selected: Color,
+    pub popup_fg: Color,
+    pub popup_bg: Color,
+    pub stale: Color,
+    pub aging: Color,
+    pub success: Color,
+    pub failure: Color,
+    // Pending checks and reviews, mentions of the user
+    pub warning: Color,
+    pub link: Color,
+    pub code: Color,
+    // Review requests, linked pull requests and pending review comments
+    pub special: Color,
+}
+
+// The `[theme]` section of the config: a built-in theme and colors overriding some of its roles
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct ThemeConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(flatten)]
+    pub colors: HashMap<String, String>,
+}
+
+impl ThemeConfig {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.colors.is_empty()
+    }
+}
+
+impl Theme {
+    pub fn dark() -> Theme {
+        Theme {
+            text: Color::White,
+            accent: Color::LightCyan,
+            on_accent: Color::Black,
+            border: Color::White,
+            secondary: Color::Gray,
+            muted: Color::DarkGray,
+            selected: Color::Yellow,
+            popup_fg: Color::White,
+            popup_bg: Color::DarkGray,
+            stale: Color::Red,
+            aging: Color::Yellow,
+            success: Color::Green,
+            failure: Color::Red,
+            warning: Color::Yellow,
+            link: Color::LightBlue,
+            code: Color::LightYellow,
+            special: Color::LightMagenta,
+        }
+    }
+
+    // For terminals with a white or pale background
+    pub fn light() -> Theme {
+        Theme {
+            text: Color::Black,
+            accent: Color::Blue,
+            on_accent: Color::White,
+            border: Color::DarkGray,
+            secondary: Color::DarkGray,
+            muted: Color::Rgb(140, 140, 140),
+            selected: Color::Magenta,
+            popup_fg: Color::Black,
+            popup_bg: Color::Rgb(225, 225, 225),
+            stale: Color::Red,
+            aging: Color::Rgb(175, 95, 0),
+            success: Color::Rgb(0, 135, 0),
+            failure: Color::Red,
+            warning: Color::Rgb(175, 95, 0),
+            link: Color::Blue,
+            code: Color::Rgb(135, 0, 135),
+            special: Color::Magenta,
+        }
+    }
+
+    // Bright colors only, popups on black
+    pub fn high_contrast() -> Theme {
+        Theme {
+            text: Color::White,
+            accent: Color::LightCyan,
+            on_accent: Color::Black,
+            border: Color::White,
+            secondary: Color::White,
+            muted: Color::Gray,
+            selected: Color::LightYellow,
+            popup_fg: Color::White,
+            popup_bg: Color::Black,
+            stale: Color::LightRed,
+            aging: Color::LightYellow,
+            success: Color::LightGreen,
+            failure: Color::LightRed,
+            warning: Color::LightYellow,
+            link: Color::LightBlue,
+            code: Color::LightYellow,
+            special: Color::LightMagenta,
+        }
+    }
+
+    pub fn named(name: &str) -> Option<Theme> {
+        match name.to_lowercase().replace(['_', ' '], "-").as_str() {
+            "dark" => Some(Theme::dark()),
+            "light" => Some(Theme::light()),
+            "high-contrast" => Some(Theme::high_contrast()),
+            _ => None,
+        }
+    }
+
+    fn role_mut(&mut self, role: &str) -> Option<&mut Color> {
+        let color = match role {
+            "text" => &mut self.text,
+            "accent" => &mut self.accent,
+            "on_accent" => &mut self.on_accent,
+            "border" => &mut self.border,
+            "secondary" => &mut self.secondary,
+            "muted" => &mut self.muted,
+            "selected" => &mut self.selected,
+            "popup_fg" => &mut self.popup_fg,
+            "popup_bg" => &mut self.popup_bg,
+            "stale" => &mut self.stale,
+            "aging" => &mut self.aging,
+            "success" => &mut self.success,
+            "failure" => &mut self.failure,
+            "warning" => &mut self.warning,
+            "link" => &mut self.link,
+            "code" => &mut self.code,
+            "special" => &mut self.special,
+            _ => return None,
+        };
+        Some(color)
+    }
+
+    // The named theme, dark by default, with the overrides of the config. Unknown names are errors
+    pub fn from_config(config: &ThemeConfig) -> Result<Theme, Box<dyn Error>> {
+        let mut theme = match &config.name {
+            Some(name) => Theme::named(name)
+                .ok_or_else(|| format!("Unknown theme \"{}\", expected one of: {}", name, THEME_NAMES.join(", ")))?,
+            None => Theme::dark(),
+        };
+        for (role, value) in &config.colors {
+            let color = parse_color(value).ok_or_else(|| format!("Invalid color \"{}\" for theme.{}", value, role))?;
+            *theme.role_mut(role).ok_or_else(|| format!("Unknown theme color \"{}\"", role))? = color;
+        }
+        Ok(theme)
+    }
+
+    pub fn popup(&self) -> Style {
+        Style::default().fg(self.popup_fg).bg(self.popup_bg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn theme(toml: &str) -> Result<Theme, Box<dyn Error>> {
+        Theme::from_config(&toml::from_str::<ThemeConfig>(toml)?)
+    }
+
+    #[test]
+    fn built_in_themes_are_found_by_name() {
+        assert_eq!(theme("").unwrap(), Theme::dark());
+        assert_eq!(theme("name = \"light\"").unwrap(), Theme::light());
+        assert_eq!(theme("name = \"High Contrast\"").unwrap(), Theme::high_contrast());
+        assert_eq!(theme("name = \"high_contrast\"").unwrap(), Theme::high_contrast());
+        for name in THEME_NAMES {
+            assert!(Theme::named(name).is_some(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn unknown_theme_is_an_error() {
+        let error = theme("name = \"solarized\"").err().unwrap().to_string();
+        assert!(error.contains("solarized"), "{}", error);
+    }
+
+    #[test]
+    fn overrides_replace_the_colors_of_their_role() {
+        let theme = theme("name = \"light\"\naccent = \"#005f87\"\npopup_bg = \"gray\"").unwrap();
+        assert_eq!(theme.accent, Color::Rgb(0, 0x5f, 0x87));
+        assert_eq!(theme.popup_bg, Color::Gray);
+        assert_eq!(theme.text, Theme::light().text);
+    }
+
+    #[test]
+    fn unknown_roles_and_invalid_colors_are_errors() {
+        assert!(theme("backgroud = \"red\"").is_err());
+        assert!(theme("accent = \"#12345\"").is_err());
+        assert!(theme("accent = \"notacolor\"").is_err());
+    }
+}
@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Label, User};
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct Rename {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CommitAuthor {
+    pub name: String,
+    pub date: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct LinkedRepository {
+    pub full_name: String,
+}
+
+// Issue or pull request that mentioned the one whose timeline is shown
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct LinkedIssue {
+    pub number: i32,
+    pub title: String,
+    pub html_url: String,
+    pub state: String,
+    #[serde(default)]
+    pub pull_request: Option<serde_json::Value>,
+    #[serde(default)]
+    pub repository: Option<LinkedRepository>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CrossReference {
+    pub issue: Option<LinkedIssue>,
+}
+
+// One entry of the /issues/{n}/timeline API, the fields present depend on the kind of event
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TimelineEvent {
+    pub event: String,
+    #[serde(default)]
+    pub actor: Option<User>,
+    #[serde(default)]
+    pub user: Option<User>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub submitted_at: Option<String>,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub label: Option<Label>,
+    #[serde(default)]
+    pub assignee: Option<User>,
+    #[serde(default)]
+    pub requested_reviewer: Option<User>,
+    #[serde(default)]
+    pub rename: Option<Rename>,
+    #[serde(default)]
+    pub source: Option<CrossReference>,
+    #[serde(default)]
+    pub sha: Option<String>,
+    #[serde(default)]
+    pub commit_id: Option<String>,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub author: Option<CommitAuthor>,
+    #[serde(default)]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub html_url: Option<String>,
+}
+
+impl TimelineEvent {
+    pub fn date(&self) -> &str {
+        self.created_at
+            .as_deref()
+            .or(self.submitted_at.as_deref())
+            .or(self.author.as_ref().map(|author| author.date.as_str()))
+            .unwrap_or("")
+    }
+
+    // Who did it: the actor of events, the author of comments, reviews and commits
+    pub fn who(&self) -> &str {
+        self.actor
+            .as_ref()
+            .or(self.user.as_ref())
+            .map(|user| user.login.as_str())
+            .or(self.author.as_ref().map(|author| author.name.as_str()))
+            .unwrap_or("someone")
+    }
+
+    pub fn linked_issue(&self) -> Option<&LinkedIssue> {
+        self.source.as_ref().and_then(|source| source.issue.as_ref())
+    }
+
+    // Page the event leads to: the issue or pull request that referenced this one, or the commit
+    pub fn link(&self) -> Option<&str> {
+        self.linked_issue()
+            .map(|issue| issue.html_url.as_str())
+            .or(self.html_url.as_deref())
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self.event.as_str() {
+            "commented" => "💬",
+            "labeled" | "unlabeled" => "🏷",
+            "assigned" | "unassigned" => "👤",
+            "renamed" => "✏️",
+            "closed" => "🔴",
+            "reopened" => "🟢",
+            "merged" => "🟣",
+            "cross-referenced" => "🔗",
+            "committed" | "referenced" => "📝",
+            "reviewed" | "review_requested" => "👀",
+            _ => "•",
+        }
+    }
+
+    pub fn description(&self) -> String {
+        let short_sha = |sha: &Option<String>| sha.as_deref().unwrap_or("").chars().take(7).collect::<String>();
+        let first_line = |text: &Option<String>| text.as_deref().unwrap_or("").lines().next().unwrap_or("").to_string();
+        let login = |user: &Option<User>| user.as_ref().map(|u| u.login.clone()).unwrap_or_default();
+        match self.event.as_str() {
+            "commented" => format!("{} commented: {}", self.who(), first_line(&self.body)),
+            "labeled" | "unlabeled" => format!(
+                "{} {} label {}",
+                self.who(),
+                if self.event == "labeled" { "added" } else { "removed" },
+                self.label.as_ref().map(|label| label.name.as_str()).unwrap_or(""),
+            ),
+            "assigned" => format!("{} assigned {}", self.who(), login(&self.assignee)),
+            "unassigned" => format!("{} unassigned {}", self.who(), login(&self.assignee)),
+            "review_requested" => format!("{} requested a review from {}", self.who(), login(&self.requested_reviewer)),
+            "reviewed" => format!(
+                "{} reviewed: {}",
+                self.who(),
+                self.state.as_deref().unwrap_or("").to_lowercase().replace('_', " "),
+            ),
+            "renamed" => match &self.rename {
+                Some(rename) => format!("{} renamed \"{}\" to \"{}\"", self.who(), rename.from, rename.to),
+                None => format!("{} renamed it", self.who()),
+            },
+            "cross-referenced" => match self.linked_issue() {
+                Some(issue) => format!(
+                    "{} mentioned this in {} {}#{} {} [{}]",
+                    self.who(),
+                    if issue.pull_request.is_some() { "pull request" } else { "issue" },
+                    issue.repository.as_ref().map(|repo| repo.full_name.as_str()).unwrap_or(""),
+                    issue.number,
+                    issue.title,
+                    issue.state,
+                ),
+                None => format!("{} mentioned this", self.who()),
+            },
+            "committed" => format!("{} committed {} {}", self.who(), short_sha(&self.sha), first_line(&self.message)),
+            "referenced" => format!("{} referenced this in commit {}", self.who(), short_sha(&self.commit_id)),
+            event => format!("{} {}", self.who(), event.replace('_', " ")),
+        }
+    }
+}
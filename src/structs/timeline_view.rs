@@ -0,0 +1,22 @@
+use tui::widgets::ListState;
+
+use super::{ApiResponseItem, TimelineEvent};
+
+// Timeline of an issue or pull request opened over the lists
+pub struct TimelineView {
+    pub item: ApiResponseItem,
+    pub events: Vec<TimelineEvent>,
+    pub state: ListState,
+}
+
+impl TimelineView {
+    pub fn new(item: ApiResponseItem, events: Vec<TimelineEvent>) -> TimelineView {
+        let mut state = ListState::default();
+        state.select(Some(0));
+        TimelineView { item, events, state }
+    }
+
+    pub fn selected(&self) -> Option<&TimelineEvent> {
+        self.state.selected().and_then(|i| self.events.get(i))
+    }
+}
@@ -1,10 +1,15 @@
-use tui::{layout::Rect, widgets::ListState};
+use chrono::{DateTime, Utc};
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+use tui::{layout::Rect, style::Color, widgets::ListState};
 
-use crate::structs::ApiResponseItem;
+use crate::structs::{ApiResponseItem, DiffLine, DiffLineKind};
 
 pub fn centered_rect(width: u16, height: u16, parent: Rect) -> Rect {
     let parent_width = parent.width;
     let parent_height = parent.height;
+    // Shrink the popup on small terminals instead of overflowing
+    let width = width.min(parent_width);
+    let height = height.min(parent_height);
 
     let x = (parent_width - width) / 2;
     let y = (parent_height - height) / 2;
@@ -12,26 +17,10 @@ pub fn centered_rect(width: u16, height: u16, parent: Rect) -> Rect {
     Rect::new(x, y, width, height)
 }
 
-pub fn get_current_state_and_list<'a>(
-    active_open: bool,
-    is_pr_review: bool,
-    issue_list_state_open: &'a mut ListState,
-    issue_list_state_closed: &'a mut ListState,
-    issue_list_state_to_review: &'a mut ListState,
-    issues_list_open: &'a Vec<ApiResponseItem>,
-    issues_list_closed: &'a Vec<ApiResponseItem>,
-    assigned_pr_list: &'a Vec<ApiResponseItem>,
-) -> (&'a mut ListState, &'a Vec<ApiResponseItem>) {
-    if is_pr_review {
-        (issue_list_state_to_review, assigned_pr_list)
-    } else if active_open {
-        (issue_list_state_open, issues_list_open)
-    } else {
-        (issue_list_state_closed, issues_list_closed)
-    }
-}
-
-pub fn move_selection(state: &mut ListState, items: &Vec<ApiResponseItem>, delta: isize) {
+pub fn move_selection<T>(state: &mut ListState, items: &[T], delta: isize) {
+  if items.is_empty() {
+      return;
+  }
   if let Some(selected) = state.selected() {
       let next = (selected as isize + delta).max(0).min((items.len() - 1) as isize);
       state.select(Some(next as usize));
@@ -45,45 +34,37 @@ pub fn get_current_state_repo_org_list<'a> (
     (org_or_repo_list_state, org_or_repo_list)
 }
 
-pub fn move_selection_org_repo(state: &mut ListState, items: &Vec<String>, delta: isize) {
+pub fn move_selection_org_repo(state: &mut ListState, items: &[String], delta: isize) {
     if let Some(selected) = state.selected() {
         let next = (selected as isize + delta).max(0).min((items.len() - 1) as isize);
         state.select(Some(next as usize));
     }
 }
 
-pub fn filter_issues_by_org(issues: &Vec<ApiResponseItem>, filter: String) -> Vec<ApiResponseItem> {
-  let mut filtered_issues: Vec<ApiResponseItem> = vec![];
-  for issue in issues {
-      if let Some(organization) = &issue.organization {
-          if organization.contains(&filter) {
-              filtered_issues.push(issue.clone());
-          }
-      }
-  }
-  filtered_issues
-}
+// Fuzzy match an item on its title, number, repository, labels and body.
+// Returns the best score and the matched chars of the title when the title is what matched
+pub fn fuzzy_match(matcher: &SkimMatcherV2, issue: &ApiResponseItem, pattern: &str) -> Option<(i64, Vec<usize>)> {
+  let title = matcher.fuzzy_indices(&issue.title, pattern);
+  let others = [
+      issue.number.to_string(),
+      format!("#{}", issue.number),
+      issue.repository.clone().unwrap_or_default(),
+      issue.labels.iter().map(|l| l.name.as_str()).collect::<Vec<_>>().join(" "),
+  ]
+  .iter()
+  .filter_map(|field| matcher.fuzzy_match(field, pattern))
+  .max();
+  // Scattered letters match almost any long body, so the body has to contain the pattern and ranks last
+  let body = issue.body
+      .as_deref()
+      .filter(|body| body.to_lowercase().contains(&pattern.to_lowercase()))
+      .map(|_| 1);
 
-pub fn filter_issues_by_repo(issues: &Vec<ApiResponseItem>, filter: String) -> Vec<ApiResponseItem> {
-  let mut filtered_issues: Vec<ApiResponseItem> = vec![];
-  for issue in issues {
-      if let Some(organization) = &issue.repository {
-          if organization.contains(&filter) {
-              filtered_issues.push(issue.clone());
-          }
-      }
+  let best_other = others.max(body);
+  match title {
+      Some((score, indices)) if best_other.map(|other| score >= other).unwrap_or(true) => Some((score, indices)),
+      _ => best_other.map(|score| (score, vec![])),
   }
-  filtered_issues
-}
-// filter issue list by pasing is_pr
-pub fn filter_issues_by_state(issues: &Vec<ApiResponseItem>, filter_pr: bool) -> Vec<ApiResponseItem> {
-  let mut filtered_issues: Vec<ApiResponseItem> = vec![];
-  for issue in issues {
-      if issue.is_pr == filter_pr {
-          filtered_issues.push(issue.clone());
-      }
-  }
-  filtered_issues
 }
 
 pub fn get_org_list(issues: &Vec<ApiResponseItem>) -> Vec<String> {
@@ -109,3 +90,296 @@ pub fn get_repo_list(issues: &Vec<ApiResponseItem>) -> Vec<String> {
   }
   repo_list
 }
+
+// Labels of the items with the number of items carrying each one, the most used first
+pub fn get_label_list(issues: &[ApiResponseItem]) -> Vec<(String, usize)> {
+  let mut label_list: Vec<(String, usize)> = vec![];
+  for label in issues.iter().flat_map(|issue| &issue.labels) {
+      match label_list.iter_mut().find(|(name, _)| *name == label.name) {
+          Some((_, count)) => *count += 1,
+          None => label_list.push((label.name.clone(), 1)),
+      }
+  }
+  label_list.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+  label_list
+}
+
+// Whether the text pings the user, "@bob" but not "@bobby", "@bob-bot" or "email@bob.com"
+pub fn mentions_user(text: &str, username: &str) -> bool {
+  let text = text.to_lowercase();
+  let mention = format!("@{}", username.to_lowercase());
+  let login_char = |c: Option<char>| c.map(|c| c.is_alphanumeric() || c == '-').unwrap_or(false);
+  text.match_indices(&mention).any(|(index, _)| {
+      !login_char(text[..index].chars().next_back()) && !login_char(text[index + mention.len()..].chars().next())
+  })
+}
+
+// Parse the start line out of one side of a hunk header, e.g. "-12,7" or "+14"
+fn parse_hunk_start(range: &str) -> i32 {
+  range.get(1..).unwrap_or("").split(',').next().and_then(|n| n.parse().ok()).unwrap_or(0)
+}
+
+// Split a unified diff patch into lines annotated with their old and new line numbers
+pub fn parse_patch(patch: &str) -> Vec<DiffLine> {
+  let mut lines: Vec<DiffLine> = vec![];
+  let mut old_line = 0;
+  let mut new_line = 0;
+  for raw in patch.lines() {
+      if raw.starts_with("@@") {
+          let ranges: Vec<&str> = raw.split_whitespace().collect();
+          if ranges.len() >= 3 {
+              old_line = parse_hunk_start(ranges[1]);
+              new_line = parse_hunk_start(ranges[2]);
+          }
+          lines.push(DiffLine { kind: DiffLineKind::Hunk, content: raw.to_string(), old_line: None, new_line: None });
+      } else if let Some(content) = raw.strip_prefix('+') {
+          lines.push(DiffLine { kind: DiffLineKind::Added, content: content.to_string(), old_line: None, new_line: Some(new_line) });
+          new_line += 1;
+      } else if let Some(content) = raw.strip_prefix('-') {
+          lines.push(DiffLine { kind: DiffLineKind::Removed, content: content.to_string(), old_line: Some(old_line), new_line: None });
+          old_line += 1;
+      } else if raw.starts_with('\\') {
+          lines.push(DiffLine { kind: DiffLineKind::Meta, content: raw.to_string(), old_line: None, new_line: None });
+      } else {
+          let content = raw.strip_prefix(' ').unwrap_or(raw);
+          lines.push(DiffLine { kind: DiffLineKind::Context, content: content.to_string(), old_line: Some(old_line), new_line: Some(new_line) });
+          old_line += 1;
+          new_line += 1;
+      }
+  }
+  lines
+}
+
+// Case insensitive glob where * matches any run of chars and ? a single one, e.g. "*[bot]"
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+  let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+  let text: Vec<char> = text.to_lowercase().chars().collect();
+  // Position after the last * in the pattern, and where the text was when it was reached
+  let (mut p, mut t) = (0, 0);
+  let mut star: Option<(usize, usize)> = None;
+  while t < text.len() {
+      if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+          p += 1;
+          t += 1;
+      } else if p < pattern.len() && pattern[p] == '*' {
+          star = Some((p + 1, t));
+          p += 1;
+      } else if let Some((star_p, star_t)) = star {
+          p = star_p;
+          t = star_t + 1;
+          star = Some((star_p, star_t + 1));
+      } else {
+          return false;
+      }
+  }
+  pattern[p..].iter().all(|c| *c == '*')
+}
+
+// Color written in the config, a name such as "red" or "lightblue", or a hex code such as "#d73a4a"
+pub fn parse_color(value: &str) -> Option<Color> {
+  if let Some(hex) = value.strip_prefix('#') {
+      let channel = |i: usize| hex.get(i..i + 2).and_then(|c| u8::from_str_radix(c, 16).ok());
+      return match (hex.len(), channel(0), channel(2), channel(4)) {
+          (6, Some(r), Some(g), Some(b)) => Some(Color::Rgb(r, g, b)),
+          _ => None,
+      };
+  }
+  let color = match value.to_lowercase().replace(['_', '-', ' '], "").as_str() {
+      "black" => Color::Black,
+      "red" => Color::Red,
+      "green" => Color::Green,
+      "yellow" => Color::Yellow,
+      "blue" => Color::Blue,
+      "magenta" => Color::Magenta,
+      "cyan" => Color::Cyan,
+      "gray" | "grey" => Color::Gray,
+      "darkgray" | "darkgrey" => Color::DarkGray,
+      "lightred" => Color::LightRed,
+      "lightgreen" => Color::LightGreen,
+      "lightyellow" => Color::LightYellow,
+      "lightblue" => Color::LightBlue,
+      "lightmagenta" => Color::LightMagenta,
+      "lightcyan" => Color::LightCyan,
+      "white" => Color::White,
+      "reset" | "default" => Color::Reset,
+      _ => return None,
+  };
+  Some(color)
+}
+
+// Compact age of a GitHub timestamp, e.g. "5m", "3d" or "2mo"
+pub fn format_age(timestamp: &str) -> String {
+  let Ok(date) = timestamp.parse::<DateTime<Utc>>() else {
+      return String::new();
+  };
+  let minutes = Utc::now().signed_duration_since(date).num_minutes().max(0);
+  match minutes {
+      0 => "now".to_string(),
+      1..=59 => format!("{}m", minutes),
+      60..=1439 => format!("{}h", minutes / 60),
+      1440..=43199 => format!("{}d", minutes / 1440),
+      43200..=525599 => format!("{}mo", minutes / 43200),
+      _ => format!("{}y", minutes / 525600),
+  }
+}
+
+pub fn format_duration(seconds: i64) -> String {
+  if seconds >= 3600 {
+      format!("{}h {}m", seconds / 3600, (seconds % 3600) / 60)
+  } else if seconds >= 60 {
+      format!("{}m {}s", seconds / 60, seconds % 60)
+  } else {
+      format!("{}s", seconds.max(0))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(title: &str, number: i32, labels: &[&str], body: &str) -> ApiResponseItem {
+        let mut item = ApiResponseItem::sample(number, title).with_labels(labels);
+        item.body = Some(body.to_string());
+        item
+    }
+
+    #[test]
+    fn hunk_start_reads_the_line_before_the_count() {
+        assert_eq!(parse_hunk_start("-12,7"), 12);
+        assert_eq!(parse_hunk_start("+14"), 14);
+        assert_eq!(parse_hunk_start("+0,0"), 0);
+        assert_eq!(parse_hunk_start(""), 0);
+    }
+
+    #[test]
+    fn patch_lines_are_numbered_on_their_side() {
+        let lines = parse_patch("@@ -10,3 +20,4 @@ fn main\n context\n-removed\n+added\n+added again\n last\n\\ No newline at end of file");
+        let kinds: Vec<DiffLineKind> = lines.iter().map(|line| line.kind).collect();
+        assert_eq!(kinds, vec![
+            DiffLineKind::Hunk,
+            DiffLineKind::Context,
+            DiffLineKind::Removed,
+            DiffLineKind::Added,
+            DiffLineKind::Added,
+            DiffLineKind::Context,
+            DiffLineKind::Meta,
+        ]);
+        let numbers: Vec<(Option<i32>, Option<i32>)> = lines.iter().map(|line| (line.old_line, line.new_line)).collect();
+        assert_eq!(numbers, vec![
+            (None, None),
+            (Some(10), Some(20)),
+            (Some(11), None),
+            (None, Some(21)),
+            (None, Some(22)),
+            (Some(12), Some(23)),
+            (None, None),
+        ]);
+        assert_eq!(lines[1].content, "context");
+        assert_eq!(lines[3].content, "added");
+    }
+
+    #[test]
+    fn each_hunk_restarts_the_numbers() {
+        let lines = parse_patch("@@ -1 +1 @@\n-a\n+b\n@@ -40,2 +41,2 @@\n x");
+        assert_eq!((lines[1].old_line, lines[2].new_line), (Some(1), Some(1)));
+        assert_eq!((lines[4].old_line, lines[4].new_line), (Some(40), Some(41)));
+    }
+
+    #[test]
+    fn fuzzy_match_highlights_the_title() {
+        let matcher = SkimMatcherV2::default();
+        let item = issue("Crash on startup", 12, &[], "");
+        let (_, indices) = fuzzy_match(&matcher, &item, "crsh").unwrap();
+        assert_eq!(indices, vec![0, 1, 3, 4]);
+    }
+
+    #[test]
+    fn fuzzy_match_falls_back_on_the_other_fields() {
+        let matcher = SkimMatcherV2::default();
+        let item = issue("Crash on startup", 1234, &["regression"], "Seen since the upgrade");
+        for pattern in ["#1234", "app", "regression", "upgrade"] {
+            let (score, indices) = fuzzy_match(&matcher, &item, pattern).unwrap();
+            assert!(score > 0, "{}", pattern);
+            assert!(indices.is_empty(), "{}", pattern);
+        }
+        assert!(fuzzy_match(&matcher, &item, "zzz").is_none());
+    }
+
+    #[test]
+    fn fuzzy_match_needs_the_whole_pattern_in_the_body() {
+        let matcher = SkimMatcherV2::default();
+        let item = issue("Title", 1, &[], "a long body with plenty of letters to scatter a pattern over");
+        assert!(fuzzy_match(&matcher, &item, "lpttrn").is_none());
+        assert_eq!(fuzzy_match(&matcher, &item, "PLENTY").map(|(score, _)| score), Some(1));
+    }
+
+    #[test]
+    fn mentions_need_the_exact_login() {
+        assert!(mentions_user("thanks @bob", "bob"));
+        assert!(mentions_user("@bob, can you look?", "bob"));
+        assert!(mentions_user("cc @Bob.", "bob"));
+        assert!(mentions_user("@bobby and then @bob", "BOB"));
+        assert!(!mentions_user("@bobby", "bob"));
+        assert!(!mentions_user("@bob-x", "bob"));
+        assert!(!mentions_user("mail email@bob.com", "bob"));
+        assert!(!mentions_user("bob", "bob"));
+    }
+
+    #[test]
+    fn glob_stars_match_any_run_of_chars() {
+        assert!(glob_match("*[bot]", "netlify[bot]"));
+        assert!(glob_match("*[bot]", "[bot]"));
+        assert!(glob_match("renovate*", "renovate-bot"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("a*b*c", "aXXbYYbZc"));
+        assert!(!glob_match("*[bot]", "netlify[bot]s"));
+        assert!(!glob_match("a*b*c", "aXXbYY"));
+    }
+
+    #[test]
+    fn glob_question_mark_matches_a_single_char() {
+        assert!(glob_match("bot?", "bot1"));
+        assert!(!glob_match("bot?", "bot"));
+        assert!(!glob_match("bot?", "bot12"));
+    }
+
+    #[test]
+    fn glob_ignores_case_and_brackets_are_plain() {
+        assert!(glob_match("Dependabot[BOT]", "dependabot[bot]"));
+        assert!(!glob_match("[ab]", "a"));
+        assert!(!glob_match("renovate", "renovate-bot"));
+    }
+
+    #[test]
+    fn colors_are_names_or_hex_codes() {
+        assert_eq!(parse_color("#d73a4a"), Some(Color::Rgb(0xd7, 0x3a, 0x4a)));
+        assert_eq!(parse_color("#FFFFFF"), Some(Color::Rgb(255, 255, 255)));
+        assert_eq!(parse_color("red"), Some(Color::Red));
+        assert_eq!(parse_color("Light Blue"), Some(Color::LightBlue));
+        assert_eq!(parse_color("dark_grey"), Some(Color::DarkGray));
+    }
+
+    #[test]
+    fn invalid_colors_are_rejected() {
+        assert_eq!(parse_color("#12345"), None);
+        assert_eq!(parse_color("#1234567"), None);
+        assert_eq!(parse_color("#gg0000"), None);
+        assert_eq!(parse_color("notacolor"), None);
+        assert_eq!(parse_color(""), None);
+    }
+
+    #[test]
+    fn labels_are_counted_once_per_issue_and_sorted_by_count_then_name() {
+        let issues = vec![
+            issue("One", 1, &["bug", "ui"], ""),
+            issue("Two", 2, &["ui", "docs"], ""),
+            issue("Three", 3, &["bug", "ui"], ""),
+        ];
+        assert_eq!(get_label_list(&issues), vec![
+            ("ui".to_string(), 3),
+            ("bug".to_string(), 2),
+            ("docs".to_string(), 1),
+        ]);
+        assert!(get_label_list(&[issue("None", 4, &[], "")]).is_empty());
+    }
+}